@@ -30,12 +30,13 @@
 //
 // Set Config struct into a CONFIG lazy_static to avoid multiple processing.
 //
-use clap::{App, Arg, SubCommand};
+use chrono_tz::Tz;
+use clap::{App, Arg, ArgMatches, SubCommand};
 use dotenv;
 use lazy_static::lazy_static;
 use log::{info, warn};
 use serde::Deserialize;
-use std::env;
+use std::{env, fmt};
 
 // Set Config struct into a CONFIG lazy_static to avoid multiple processing
 lazy_static! {
@@ -52,12 +53,29 @@ fn default_error_interval() -> u32 {
     2
 }
 
+/// provides default value for min_run_interval_seconds if CRUNCH_MIN_RUN_INTERVAL_SECONDS env var is not set
+/// note: 0 means that consecutive era-triggered runs are never skipped
+fn default_min_run_interval_seconds() -> u64 {
+    0
+}
+
+/// provides default value for min_random_wait if CRUNCH_MIN_RANDOM_WAIT env var is not set
+fn default_min_random_wait() -> u64 {
+    0
+}
+
+/// provides default value for max_random_wait if CRUNCH_MAX_RANDOM_WAIT env var is not set
+fn default_max_random_wait() -> u64 {
+    240
+}
+
 /// provides default value for seed_path if CRUNCH_SEED_PATH env var is not set
 fn default_seed_path() -> String {
     ".private.seed".into()
 }
 
 /// provides default value for maximum_payouts if CRUNCH_MAXIMUM_PAYOUTS env var is not set
+/// note: 0 means unlimited
 fn default_maximum_payouts() -> u32 {
     4
 }
@@ -67,9 +85,78 @@ fn default_maximum_history_eras() -> u32 {
     4
 }
 
+/// provides default value for era_chunk_size if CRUNCH_ERA_CHUNK_SIZE env var is not set
+/// note: 0 means that all history eras are scanned before any payout is submitted
+fn default_era_chunk_size() -> u32 {
+    0
+}
+
+/// provides default value for era_scan_concurrency if CRUNCH_ERA_SCAN_CONCURRENCY env var is not set
+fn default_era_scan_concurrency() -> u32 {
+    1
+}
+
+/// provides default value for max_stashes_per_run if CRUNCH_MAX_STASHES_PER_RUN env var is not set
+/// note: 0 means that all stashes are processed in every run
+fn default_max_stashes_per_run() -> u32 {
+    0
+}
+
+/// provides default value for state_path if CRUNCH_STATE_PATH env var is not set
+fn default_state_path() -> String {
+    ".crunch.state.json".into()
+}
+
 /// provides default value for maximum_calls if CRUNCH_MAXIMUM_CALLS env var is not set
+/// note: 0 means that the default defined for each network is used
 fn default_maximum_calls() -> u32 {
-    2
+    0
+}
+
+/// provides default value for metrics_port if CRUNCH_METRICS_PORT env var is not set
+fn default_metrics_port() -> u16 {
+    9616
+}
+
+/// provides default value for export_path if CRUNCH_EXPORT_PATH env var is not set
+fn default_export_path() -> String {
+    ".crunch.export.jsonl".into()
+}
+
+/// provides default value for max_total_calls if CRUNCH_MAX_TOTAL_CALLS env var is not set
+/// note: 0 means that there is no limit on the total number of calls in a run
+fn default_max_total_calls() -> u32 {
+    0
+}
+
+/// provides default value for stash_sources if CRUNCH_STASH_SOURCES env var is not set
+fn default_stash_sources() -> Vec<StashSource> {
+    vec![StashSource::Config, StashSource::Remote, StashSource::Pools]
+}
+
+/// provides default value for pool_nominee_era_offset if CRUNCH_POOL_NOMINEE_ERA_OFFSET env var is not set
+fn default_pool_nominee_era_offset() -> u32 {
+    1
+}
+
+/// provides default value for batch_size_strategy if CRUNCH_BATCH_SIZE_STRATEGY env var is not set
+fn default_batch_size_strategy() -> BatchSizeStrategy {
+    BatchSizeStrategy::Fixed
+}
+
+/// provides default value for report_sort if CRUNCH_REPORT_SORT env var is not set
+fn default_report_sort() -> ReportSort {
+    ReportSort::Name
+}
+
+/// provides default value for max_batch_fee if CRUNCH_MAX_BATCH_FEE env var is not set
+fn default_max_batch_fee() -> u64 {
+    0
+}
+
+/// provides default value for max_weight_fraction if CRUNCH_MAX_WEIGHT_FRACTION env var is not set
+fn default_max_weight_fraction() -> f32 {
+    1.0
 }
 
 /// provides default value for existential_deposit_factor_warning if CRUNCH_EXISTENTIAL_DEPOSIT_FACTOR_WARNING env var is not set
@@ -79,6 +166,16 @@ fn default_existential_deposit_factor_warning() -> u32 {
     2
 }
 
+/// provides default value for high_commission_warn if CRUNCH_HIGH_COMMISSION_WARN env var is not set
+fn default_high_commission_warn() -> u32 {
+    100
+}
+
+/// provides the default number of seconds to wait for the external signer command
+fn default_external_signer_timeout() -> u64 {
+    120
+}
+
 /// provides the default tip in PLANCKS for the block author
 fn default_tx_tip() -> u64 {
     0
@@ -94,9 +191,15 @@ fn default_pool_compound_threshold() -> u64 {
     100000000000
 }
 
+/// provides default value for operator_compound_requires_permission if CRUNCH_OPERATOR_COMPOUND_REQUIRES_PERMISSION env var is not set
+fn default_operator_compound_requires_permission() -> bool {
+    true
+}
+
 /// provides default value for maximum_pool_members_calls if CRUNCH_MAXIMUM_POOL_MEMBERS_CALLS env var is not set
+/// note: 0 means that the default defined for each network is used
 fn default_maximum_pool_members_calls() -> u32 {
-    128
+    0
 }
 
 /// provides default value for onet_api_key if CRUNCH_ONET_API_KEY env var is not set
@@ -104,6 +207,26 @@ fn default_onet_api_key() -> String {
     "crunch-101".into()
 }
 
+/// provides default value for price_api_url if CRUNCH_PRICE_API_URL env var is not set
+fn default_price_api_url() -> String {
+    "https://api.coingecko.com/api/v3/simple/price".into()
+}
+
+/// provides default value for report_validator_url_template if CRUNCH_REPORT_VALIDATOR_URL_TEMPLATE env var is not set
+fn default_report_validator_url_template() -> String {
+    "https://{network}.subscan.io/validator/{stash}".into()
+}
+
+/// provides default value for report_extrinsic_url_template if CRUNCH_REPORT_EXTRINSIC_URL_TEMPLATE env var is not set
+fn default_report_extrinsic_url_template() -> String {
+    "https://{network}.subscan.io/extrinsic/{extrinsic}".into()
+}
+
+/// provides default value for timezone if CRUNCH_TIMEZONE env var is not set
+fn default_timezone() -> String {
+    "UTC".into()
+}
+
 /// provides default value for onet_number_last_sessions if CRUNCH_ONET_NUMBER_LAST_SESSIONS env var is not set
 fn default_onet_number_last_sessions() -> u32 {
     6
@@ -114,49 +237,149 @@ fn default_run_mode() -> RunMode {
     RunMode::Era
 }
 
-#[derive(Clone, Deserialize, Debug)]
+#[derive(Clone, Deserialize)]
 pub struct Config {
     #[serde(default = "default_interval")]
     pub interval: u64,
     #[serde(default = "default_error_interval")]
     pub error_interval: u32,
+    #[serde(default = "default_min_run_interval_seconds")]
+    pub min_run_interval_seconds: u64,
+    #[serde(default)]
+    pub keepalive_seconds: u64,
+    #[serde(default = "default_min_random_wait")]
+    pub min_random_wait: u64,
+    #[serde(default = "default_max_random_wait")]
+    pub max_random_wait: u64,
     #[serde(default)]
     pub substrate_ws_url: String,
     #[serde(default)]
     pub substrate_people_ws_url: String,
     #[serde(default)]
     pub stashes_url: String,
+    #[serde(default = "default_stash_sources")]
+    pub stash_sources: Vec<StashSource>,
     #[serde(default)]
     pub github_pat: String,
     #[serde(default)]
     pub pool_ids: Vec<u32>,
     #[serde(default)]
+    pub strict_pools: bool,
+    #[serde(default)]
     pub pool_active_nominees_payout_enabled: bool,
+    #[serde(default = "default_pool_nominee_era_offset")]
+    pub pool_nominee_era_offset: u32,
     #[serde(default)]
     pub pool_all_nominees_payout_enabled: bool,
     #[serde(default)]
     pub pool_members_compound_enabled: bool,
     #[serde(default)]
     pub pool_only_operator_compound_enabled: bool,
+    #[serde(default = "default_operator_compound_requires_permission")]
+    pub operator_compound_requires_permission: bool,
+    #[serde(default)]
+    pub pool_operator_membership_check_enabled: bool,
     #[serde(default = "default_pool_compound_threshold")]
     pub pool_compound_threshold: u64,
+    #[serde(default)]
+    pub pool_compound_min_interval_eras: u32,
     #[serde(default = "default_maximum_pool_members_calls")]
     pub maximum_pool_members_calls: u32,
     #[serde(default)]
     pub unique_stashes_enabled: bool,
     #[serde(default)]
     pub group_identity_enabled: bool,
+    #[serde(default)]
+    pub group_identity_threshold: u32,
     #[serde(default = "default_seed_path")]
     pub seed_path: String,
+    #[serde(default)]
+    pub keystore_password: String,
+    // external signer
+    #[serde(default)]
+    pub external_signer_cmd: String,
+    #[serde(default)]
+    pub external_signer_account: String,
+    #[serde(default = "default_external_signer_timeout")]
+    pub external_signer_timeout: u64,
+    // signer top-up
+    #[serde(default)]
+    pub funding_seed_path: String,
+    #[serde(default)]
+    pub signer_topup_threshold: u64,
+    #[serde(default)]
+    pub signer_topup_amount: u64,
     pub stashes: Vec<String>,
     #[serde(default = "default_maximum_payouts")]
     pub maximum_payouts: u32,
+    #[serde(default)]
+    pub maximum_payouts_overrides: String,
     #[serde(default = "default_maximum_history_eras")]
     pub maximum_history_eras: u32,
+    #[serde(default)]
+    pub skip_recent_eras: u32,
+    #[serde(default)]
+    pub respect_max_eras_in_verbose: bool,
+    #[serde(default = "default_era_chunk_size")]
+    pub era_chunk_size: u32,
+    #[serde(default)]
+    pub latest_era_only: bool,
+    #[serde(default = "default_era_scan_concurrency")]
+    pub era_scan_concurrency: u32,
+    #[serde(default)]
+    pub skip_inactive_eras_enabled: bool,
+    #[serde(default)]
+    pub skip_legacy_claimed_enabled: bool,
+    #[serde(default)]
+    pub skip_zero_reward_eras_enabled: bool,
+    #[serde(default)]
+    pub confirm_rewards_enabled: bool,
+    #[serde(default)]
+    pub continue_on_batch_failure_enabled: bool,
+    #[serde(default)]
+    pub dry_run_enabled: bool,
+    #[serde(default)]
+    pub log_all_events_enabled: bool,
+    #[serde(default)]
+    pub risk_check_enabled: bool,
+    #[serde(default)]
+    pub export_enabled: bool,
+    #[serde(default = "default_export_path")]
+    pub export_path: String,
+    #[serde(default)]
+    pub export_raw_points: bool,
+    #[serde(default)]
+    pub export_nominator_detail: bool,
+    #[serde(default)]
+    pub metrics_enabled: bool,
+    #[serde(default = "default_metrics_port")]
+    pub metrics_port: u16,
+    #[serde(default)]
+    pub dashboard_port: u16,
+    #[serde(default = "default_max_stashes_per_run")]
+    pub max_stashes_per_run: u32,
+    #[serde(default = "default_state_path")]
+    pub state_path: String,
+    #[serde(default)]
+    pub pause_file: String,
+    #[serde(default)]
+    pub era_log_size: u32,
     #[serde(default = "default_maximum_calls")]
     pub maximum_calls: u32,
+    #[serde(default = "default_max_total_calls")]
+    pub max_total_calls: u32,
+    #[serde(default)]
+    pub only_pages: String,
+    #[serde(default = "default_batch_size_strategy")]
+    pub batch_size_strategy: BatchSizeStrategy,
+    #[serde(default = "default_max_batch_fee")]
+    pub max_batch_fee: u64,
+    #[serde(default = "default_max_weight_fraction")]
+    pub max_weight_fraction: f32,
     #[serde(default = "default_existential_deposit_factor_warning")]
     pub existential_deposit_factor_warning: u32,
+    #[serde(default = "default_high_commission_warn")]
+    pub high_commission_warn: u32,
     #[serde(default = "default_tx_tip")]
     pub tx_tip: u64,
     #[serde(default = "default_tx_mortal_period")]
@@ -164,8 +387,22 @@ pub struct Config {
     #[serde(default)]
     pub only_view: bool,
     #[serde(default)]
+    pub only_notify_test: bool,
+    #[serde(default)]
+    pub only_pending: bool,
+    #[serde(default)]
+    pub only_dump_metadata: bool,
+    #[serde(default)]
+    pub dump_metadata_path: String,
+    #[serde(default)]
+    pub at_block: String,
+    #[serde(default)]
+    pub view_json_enabled: bool,
+    #[serde(default)]
     pub is_debug: bool,
     #[serde(default)]
+    pub no_color: bool,
+    #[serde(default)]
     pub is_boring: bool,
     #[serde(default)]
     pub is_short: bool,
@@ -182,6 +419,36 @@ pub struct Config {
     pub onet_api_key: String,
     #[serde(default = "default_onet_number_last_sessions")]
     pub onet_number_last_sessions: u32,
+    // custom report template
+    #[serde(default)]
+    pub report_template_path: String,
+    #[serde(default = "default_report_sort")]
+    pub report_sort: ReportSort,
+    #[serde(default)]
+    pub identity_max_len: usize,
+    #[serde(default)]
+    pub strip_emoji: bool,
+    #[serde(default)]
+    pub report_minimal_on_success: bool,
+    #[serde(default)]
+    pub report_include_controller: bool,
+    #[serde(default)]
+    pub report_compare_previous_run: bool,
+    #[serde(default)]
+    pub report_split_pools: bool,
+    #[serde(default = "default_report_validator_url_template")]
+    pub report_validator_url_template: String,
+    #[serde(default = "default_report_extrinsic_url_template")]
+    pub report_extrinsic_url_template: String,
+    // fiat conversion
+    #[serde(default)]
+    pub fiat_currency: String,
+    #[serde(default)]
+    pub locale: String,
+    #[serde(default = "default_timezone")]
+    pub timezone: String,
+    #[serde(default = "default_price_api_url")]
+    pub price_api_url: String,
     // matrix configuration
     #[serde(default)]
     pub matrix_user: String,
@@ -194,7 +461,11 @@ pub struct Config {
     #[serde(default)]
     pub matrix_public_room_disabled: bool,
     #[serde(default)]
+    pub matrix_public_room_redacted_enabled: bool,
+    #[serde(default)]
     pub matrix_bot_display_name_disabled: bool,
+    #[serde(default)]
+    pub matrix_alert_room: String,
     // light client configuration
     #[serde(default)]
     pub light_client_enabled: bool,
@@ -202,6 +473,222 @@ pub struct Config {
     pub chain_name: String,
 }
 
+// Environment variables holding secret values
+const SECRET_ENV_VARS: [&str; 6] = [
+    "CRUNCH_GITHUB_PAT",
+    "CRUNCH_SEED_PATH",
+    "CRUNCH_FUNDING_SEED_PATH",
+    "CRUNCH_KEYSTORE_PASSWORD",
+    "CRUNCH_ONET_API_KEY",
+    "CRUNCH_MATRIX_BOT_PASSWORD",
+];
+
+/// Returns the secret values defined in the environment
+fn secret_env_values() -> Vec<String> {
+    SECRET_ENV_VARS
+        .iter()
+        .filter_map(|key| env::var(key).ok())
+        .collect()
+}
+
+/// Replace any of the secret values found in the message
+fn redact_secrets(message: String, secrets: &[String]) -> String {
+    secrets
+        .iter()
+        .filter(|value| !value.is_empty())
+        .fold(message, |message, value| message.replace(value, "***"))
+}
+
+/// Redact secret values so that they never get printed
+fn redact(value: &str) -> &str {
+    if value.is_empty() {
+        ""
+    } else {
+        "***"
+    }
+}
+
+/// Debug implementation with secrets (e.g. passwords, tokens and seed paths) redacted
+impl fmt::Debug for Config {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Config")
+            .field("interval", &self.interval)
+            .field("error_interval", &self.error_interval)
+            .field("min_run_interval_seconds", &self.min_run_interval_seconds)
+            .field("keepalive_seconds", &self.keepalive_seconds)
+            .field("min_random_wait", &self.min_random_wait)
+            .field("max_random_wait", &self.max_random_wait)
+            .field("substrate_ws_url", &self.substrate_ws_url)
+            .field("substrate_people_ws_url", &self.substrate_people_ws_url)
+            .field("stashes_url", &self.stashes_url)
+            .field("stash_sources", &self.stash_sources)
+            .field("github_pat", &redact(&self.github_pat))
+            .field("pool_ids", &self.pool_ids)
+            .field("strict_pools", &self.strict_pools)
+            .field(
+                "pool_active_nominees_payout_enabled",
+                &self.pool_active_nominees_payout_enabled,
+            )
+            .field("pool_nominee_era_offset", &self.pool_nominee_era_offset)
+            .field(
+                "pool_all_nominees_payout_enabled",
+                &self.pool_all_nominees_payout_enabled,
+            )
+            .field(
+                "pool_members_compound_enabled",
+                &self.pool_members_compound_enabled,
+            )
+            .field(
+                "pool_only_operator_compound_enabled",
+                &self.pool_only_operator_compound_enabled,
+            )
+            .field(
+                "operator_compound_requires_permission",
+                &self.operator_compound_requires_permission,
+            )
+            .field(
+                "pool_operator_membership_check_enabled",
+                &self.pool_operator_membership_check_enabled,
+            )
+            .field("pool_compound_threshold", &self.pool_compound_threshold)
+            .field(
+                "pool_compound_min_interval_eras",
+                &self.pool_compound_min_interval_eras,
+            )
+            .field(
+                "maximum_pool_members_calls",
+                &self.maximum_pool_members_calls,
+            )
+            .field("unique_stashes_enabled", &self.unique_stashes_enabled)
+            .field("group_identity_enabled", &self.group_identity_enabled)
+            .field("group_identity_threshold", &self.group_identity_threshold)
+            .field("seed_path", &redact(&self.seed_path))
+            .field("keystore_password", &redact(&self.keystore_password))
+            .field("external_signer_cmd", &self.external_signer_cmd)
+            .field("external_signer_account", &self.external_signer_account)
+            .field("external_signer_timeout", &self.external_signer_timeout)
+            .field("funding_seed_path", &redact(&self.funding_seed_path))
+            .field("signer_topup_threshold", &self.signer_topup_threshold)
+            .field("signer_topup_amount", &self.signer_topup_amount)
+            .field("stashes", &self.stashes)
+            .field("maximum_payouts", &self.maximum_payouts)
+            .field("maximum_payouts_overrides", &self.maximum_payouts_overrides)
+            .field("maximum_history_eras", &self.maximum_history_eras)
+            .field("skip_recent_eras", &self.skip_recent_eras)
+            .field(
+                "respect_max_eras_in_verbose",
+                &self.respect_max_eras_in_verbose,
+            )
+            .field("era_chunk_size", &self.era_chunk_size)
+            .field("latest_era_only", &self.latest_era_only)
+            .field("era_scan_concurrency", &self.era_scan_concurrency)
+            .field(
+                "skip_inactive_eras_enabled",
+                &self.skip_inactive_eras_enabled,
+            )
+            .field(
+                "skip_legacy_claimed_enabled",
+                &self.skip_legacy_claimed_enabled,
+            )
+            .field(
+                "skip_zero_reward_eras_enabled",
+                &self.skip_zero_reward_eras_enabled,
+            )
+            .field("confirm_rewards_enabled", &self.confirm_rewards_enabled)
+            .field(
+                "continue_on_batch_failure_enabled",
+                &self.continue_on_batch_failure_enabled,
+            )
+            .field("dry_run_enabled", &self.dry_run_enabled)
+            .field("log_all_events_enabled", &self.log_all_events_enabled)
+            .field("risk_check_enabled", &self.risk_check_enabled)
+            .field("max_stashes_per_run", &self.max_stashes_per_run)
+            .field("state_path", &self.state_path)
+            .field("pause_file", &self.pause_file)
+            .field("era_log_size", &self.era_log_size)
+            .field("maximum_calls", &self.maximum_calls)
+            .field("max_total_calls", &self.max_total_calls)
+            .field("only_pages", &self.only_pages)
+            .field("export_enabled", &self.export_enabled)
+            .field("export_path", &self.export_path)
+            .field("export_raw_points", &self.export_raw_points)
+            .field("export_nominator_detail", &self.export_nominator_detail)
+            .field("metrics_enabled", &self.metrics_enabled)
+            .field("metrics_port", &self.metrics_port)
+            .field("dashboard_port", &self.dashboard_port)
+            .field("batch_size_strategy", &self.batch_size_strategy)
+            .field("max_batch_fee", &self.max_batch_fee)
+            .field("max_weight_fraction", &self.max_weight_fraction)
+            .field(
+                "existential_deposit_factor_warning",
+                &self.existential_deposit_factor_warning,
+            )
+            .field("high_commission_warn", &self.high_commission_warn)
+            .field("tx_tip", &self.tx_tip)
+            .field("tx_mortal_period", &self.tx_mortal_period)
+            .field("only_view", &self.only_view)
+            .field("only_notify_test", &self.only_notify_test)
+            .field("only_pending", &self.only_pending)
+            .field("only_dump_metadata", &self.only_dump_metadata)
+            .field("dump_metadata_path", &self.dump_metadata_path)
+            .field("at_block", &self.at_block)
+            .field("view_json_enabled", &self.view_json_enabled)
+            .field("is_debug", &self.is_debug)
+            .field("no_color", &self.no_color)
+            .field("is_boring", &self.is_boring)
+            .field("is_short", &self.is_short)
+            .field("is_medium", &self.is_medium)
+            .field("run_mode", &self.run_mode)
+            .field("onet_api_enabled", &self.onet_api_enabled)
+            .field("onet_api_url", &self.onet_api_url)
+            .field("onet_api_key", &redact(&self.onet_api_key))
+            .field("onet_number_last_sessions", &self.onet_number_last_sessions)
+            .field("report_template_path", &self.report_template_path)
+            .field("report_sort", &self.report_sort)
+            .field("identity_max_len", &self.identity_max_len)
+            .field("strip_emoji", &self.strip_emoji)
+            .field("report_minimal_on_success", &self.report_minimal_on_success)
+            .field("report_include_controller", &self.report_include_controller)
+            .field(
+                "report_compare_previous_run",
+                &self.report_compare_previous_run,
+            )
+            .field("report_split_pools", &self.report_split_pools)
+            .field(
+                "report_validator_url_template",
+                &self.report_validator_url_template,
+            )
+            .field(
+                "report_extrinsic_url_template",
+                &self.report_extrinsic_url_template,
+            )
+            .field("fiat_currency", &self.fiat_currency)
+            .field("locale", &self.locale)
+            .field("timezone", &self.timezone)
+            .field("price_api_url", &self.price_api_url)
+            .field("matrix_user", &self.matrix_user)
+            .field("matrix_bot_user", &self.matrix_bot_user)
+            .field("matrix_bot_password", &redact(&self.matrix_bot_password))
+            .field("matrix_disabled", &self.matrix_disabled)
+            .field(
+                "matrix_public_room_disabled",
+                &self.matrix_public_room_disabled,
+            )
+            .field(
+                "matrix_public_room_redacted_enabled",
+                &self.matrix_public_room_redacted_enabled,
+            )
+            .field(
+                "matrix_bot_display_name_disabled",
+                &self.matrix_bot_display_name_disabled,
+            )
+            .field("matrix_alert_room", &self.matrix_alert_room)
+            .field("light_client_enabled", &self.light_client_enabled)
+            .field("chain_name", &self.chain_name)
+            .finish()
+    }
+}
+
 #[derive(Default, Deserialize, Debug, Clone, Copy, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum RunMode {
@@ -212,10 +699,54 @@ pub enum RunMode {
     Once,
 }
 
-/// Inject dotenv and env vars into the Config struct
-fn get_config() -> Config {
-    // Define CLI flags with clap
-    let matches = App::new(env!("CARGO_PKG_NAME"))
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum StashSource {
+    // Stashes defined by 'stashes'
+    Config,
+    // Stashes fetched from 'stashes_url'
+    Remote,
+    // Stashes nominated by the pools defined by 'pool_ids'
+    Pools,
+}
+
+impl std::fmt::Display for StashSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Config => write!(f, "config"),
+            Self::Remote => write!(f, "remote"),
+            Self::Pools => write!(f, "pools"),
+        }
+    }
+}
+
+#[derive(Default, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum BatchSizeStrategy {
+    // Batches are sized by 'maximum_calls'
+    #[default]
+    Fixed,
+    // Batches are sized to fit the maximum weight of a single extrinsic
+    Weight,
+    // Batches are sized to keep the estimated fee under 'max_batch_fee'
+    Fee,
+}
+
+#[derive(Default, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportSort {
+    // Validators sorted by identity name, with the ones with warnings last
+    #[default]
+    Name,
+    // Validators with the largest reward first
+    Reward,
+    // Validators with the most unclaimed eras first
+    Unclaimed,
+}
+
+/// Define CLI flags with clap
+fn app() -> App<'static, 'static> {
+    App::new(env!("CARGO_PKG_NAME"))
     .version(env!("CARGO_PKG_VERSION"))
     .author(env!("CARGO_PKG_AUTHORS"))
     .about(env!("CARGO_PKG_DESCRIPTION"))
@@ -245,28 +776,217 @@ fn get_config() -> Config {
           .takes_value(true)
           .value_name("FILE")
           .help(
-            "Sets a custom seed file path. The seed file contains the private seed phrase to Sign the extrinsic payout call.",
+            "Sets a custom seed file path. The seed file contains the private seed phrase to Sign the extrinsic payout call. An sr25519 JSON keystore file exported from polkadot.js is also supported, decrypted with the password defined by CRUNCH_KEYSTORE_PASSWORD.",
+          ))
+      .arg(
+        Arg::with_name("external-signer-cmd")
+          .long("external-signer-cmd")
+          .takes_value(true)
+          .value_name("CMD")
+          .help(
+            "Sets an external command to Sign the extrinsic payout call instead of using the seed file. The command receives the signer payload hex encoded on stdin and must write back the sr25519 signature hex encoded on stdout. Requires 'external-signer-account'.",
+          ))
+      .arg(
+        Arg::with_name("external-signer-account")
+          .long("external-signer-account")
+          .takes_value(true)
+          .help(
+            "The account (SS58 address) of the external signer defined by 'external-signer-cmd'.",
+          ))
+      .arg(
+        Arg::with_name("external-signer-timeout")
+          .long("external-signer-timeout")
+          .takes_value(true)
+          .help(
+            "Maximum number of seconds to wait for the external signer command to return the signature.",
+          ))
+      .arg(
+        Arg::with_name("funding-seed-path")
+          .long("funding-seed-path")
+          .takes_value(true)
+          .value_name("FILE")
+          .help(
+            "Sets the seed file path of a funding account used to top up the signer account. When the signer free balance drops below 'signer-topup-threshold', 'signer-topup-amount' is transferred from the funding account to the signer before running payouts.",
+          ))
+      .arg(
+        Arg::with_name("signer-topup-threshold")
+          .long("signer-topup-threshold")
+          .takes_value(true)
+          .help(
+            "Signer free balance in PLANCKS below which the signer account is topped up from the funding account defined by 'funding-seed-path'. [default: 0]",
+          ))
+      .arg(
+        Arg::with_name("signer-topup-amount")
+          .long("signer-topup-amount")
+          .takes_value(true)
+          .help(
+            "Amount in PLANCKS transferred from the funding account to the signer account on each top-up. A value of 0 disables the top-up. [default: 0]",
           ))
       .arg(
         Arg::with_name("maximum-payouts")
           .short("m")
           .long("maximum-payouts")
           .takes_value(true)
-          .help("Maximum number of unclaimed eras for which an extrinsic payout will be submitted. (e.g. a value of 4 means that if there are unclaimed eras in the last 84 the maximum unclaimed payout calls for each stash address will be 4)."))
+          .help("Maximum number of unclaimed eras for which an extrinsic payout will be submitted. (e.g. a value of 4 means that if there are unclaimed eras in the last 84 the maximum unclaimed payout calls for each stash address will be 4). A value of 0 means unlimited, all unclaimed eras found within 'maximum-history-eras' will be claimed. [default: 4]"))
+      .arg(
+        Arg::with_name("maximum-payouts-overrides")
+          .long("maximum-payouts-overrides")
+          .takes_value(true)
+          .help("Maximum number of unclaimed eras for which an extrinsic payout will be submitted for specific stash addresses, overriding 'maximum-payouts' (e.g. stash_1:0,stash_2:8). A value of 0 means unlimited. Stashes not listed use 'maximum-payouts'."))
       .arg(
         Arg::with_name("maximum-history-eras")
               .long("maximum-history-eras")
               .takes_value(true)
               .help("Maximum number of history eras for which crunch will look for unclaimed rewards. The maximum value supported is the one defined by the constant history_depth - usually 84 - (e.g. a value of 4 means that crunch will only check in latest 4 eras if there are any unclaimed rewards for each stash address). [default: 4]"))
+      .arg(
+        Arg::with_name("skip-recent-eras")
+              .long("skip-recent-eras")
+              .takes_value(true)
+              .help("Number of the most recent eras excluded from the unclaimed rewards scan, for chains where the rewards of the latest eras are not yet available to be claimed (e.g. a value of 1 means that the era that just ended is only claimed in the next run). [default: 0]"))
+      .arg(
+        Arg::with_name("respect-max-eras-in-verbose")
+              .long("respect-max-eras-in-verbose")
+              .help("Apply 'maximum-history-eras' also when 'short' or 'medium' flags are not used. (e.g. with this flag active 'crunch' will not look for unclaimed rewards in all history eras even if running with the most verbose messages/notifications)"))
+      .arg(
+        Arg::with_name("era-chunk-size")
+              .long("era-chunk-size")
+              .takes_value(true)
+              .help("Number of history eras scanned for unclaimed rewards before submitting the respective payouts. Payouts are submitted chunk by chunk, from the oldest to the latest eras, instead of only after all history eras have been scanned (e.g. a value of 7 means that crunch will look for unclaimed rewards in 7 eras at a time). A value of 0 disables chunked scanning. [default: 0]"))
+      .arg(
+        Arg::with_name("latest-era-only")
+              .long("latest-era-only")
+              .help("Only look for unclaimed rewards in the latest completed era (active era - 1), skipping the history eras entirely. This is the fastest mode for operators running 'crunch' every era."))
+      .arg(
+        Arg::with_name("era-scan-concurrency")
+              .long("era-scan-concurrency")
+              .takes_value(true)
+              .help("Maximum number of history eras scanned in parallel for each stash when looking for unclaimed rewards. Higher values speed up the scan against high-latency RPC endpoints. [default: 1]"))
+      .arg(
+        Arg::with_name("enable-skip-inactive-eras")
+              .long("enable-skip-inactive-eras")
+              .help("Skip history eras where the stash was not in the active set (had no exposure) before looking up its claimed and unclaimed pages. This avoids unnecessary storage requests when scanning for unclaimed rewards."))
+      .arg(
+        Arg::with_name("enable-skip-legacy-claimed")
+              .long("enable-skip-legacy-claimed")
+              .help("Skip the staking ledger lookup of the legacy claimed rewards and look up the paged claimed rewards only. This avoids one storage request per stash on chains that no longer use legacy claimed rewards."))
+      .arg(
+        Arg::with_name("enable-skip-zero-reward-eras")
+              .long("enable-skip-zero-reward-eras")
+              .help("Skip history eras where the stash earned no reward points, and hence no rewards, instead of claiming them. This avoids paying fees for payouts with nothing to claim."))
+      .arg(
+        Arg::with_name("enable-confirm-rewards")
+              .long("enable-confirm-rewards")
+              .help("Confirm that the rewards of each payout were actually credited, by cross checking the 'Rewarded' amounts with the balance deposits in the same batch. Any discrepancy (e.g. rewards restaked to a pool or sent to no destination) is flagged in the report."))
+      .arg(
+        Arg::with_name("enable-continue-on-batch-failure")
+              .long("enable-continue-on-batch-failure")
+              .help("Continue the run when a batch fails with ExtrinsicFailed, instead of aborting it. The failed batch is logged and shown in the report, and the remaining batches are still submitted."))
+      .arg(
+        Arg::with_name("dry-run")
+              .long("dry-run")
+              .help("Simulate the payouts without signing or submitting any extrinsic. The call data and the estimated fee of every batch that would be submitted are logged, and the report is still generated with a DRY RUN banner. Pool members compound batches are logged the same way and signer top-ups are skipped. Note that the 'weight' and 'fee' batch size strategies still sign batches locally to estimate them, but never submit them."))
+      .arg(
+        Arg::with_name("enable-log-all-events")
+              .long("enable-log-all-events")
+              .help("Log every event of the blocks where the payout and compound batches are included (pallet, variant and fields), not only the events handled by crunch. Events are logged at debug level, so it should be used together with --debug."))
+      .arg(
+        Arg::with_name("enable-risk-check")
+              .long("enable-risk-check")
+              .help("Check if each validator has been chilled or has a slash recorded that may still be pending to be applied, and add a warning to the report when a validator is at risk."))
+      .arg(
+        Arg::with_name("enable-export")
+              .long("enable-export")
+              .help("Export the data of each run (network, summary, validators, claimed and unclaimed eras and payouts) as a single JSON line appended to the export file."))
+      .arg(
+        Arg::with_name("export-path")
+              .long("export-path")
+              .takes_value(true)
+              .value_name("FILE")
+              .help("Sets a custom export file path. [default: .crunch.export.jsonl]"))
+      .arg(
+        Arg::with_name("export-raw-points")
+              .long("export-raw-points")
+              .help("Include the reward points of every validator in the era of each payout in the export, so that the points distribution can be analysed. Note: this considerably increases the size of each export line."))
+      .arg(
+        Arg::with_name("export-nominator-detail")
+              .long("export-nominator-detail")
+              .help("Include the account and amount of every nominator rewarded in each payout in the export, so that rewards can be accounted per nominator. Note: nominator detail is only exported, never included in the report messages."))
+      .arg(
+        Arg::with_name("enable-metrics")
+              .long("enable-metrics")
+              .help("Serve run metrics (payout calls, fees paid, last run timestamp, last era processed and signer balance) labeled by network, in the OpenMetrics text format at 'http://0.0.0.0:<metrics-port>/metrics'."))
+      .arg(
+        Arg::with_name("metrics-port")
+              .long("metrics-port")
+              .takes_value(true)
+              .help("Sets the port of the metrics server. [default: 9616]"))
+      .arg(
+        Arg::with_name("dashboard-port")
+              .long("dashboard-port")
+              .takes_value(true)
+              .help("Serve a status page at 'http://0.0.0.0:<dashboard-port>' showing the connected chain, active era, signer balance, last run summary, pending unclaimed pages per stash and recent errors. By default the dashboard is disabled. [default: 0]"))
+      .arg(
+        Arg::with_name("max-stashes-per-run")
+              .long("max-stashes-per-run")
+              .takes_value(true)
+              .help("Maximum number of stashes processed in a single run. When set, each run processes the next window of stashes in rotation, so that across successive runs all stashes are eventually covered. The rotation cursor is persisted in the state file. A value of 0 processes all stashes in every run. [default: 0]"))
+      .arg(
+        Arg::with_name("state-path")
+              .long("state-path")
+              .takes_value(true)
+              .value_name("FILE")
+              .help("Sets a custom state file path. The state file is used to persist data between runs (e.g. the stashes rotation cursor). [default: .crunch.state.json]"))
+      .arg(
+        Arg::with_name("pause-file")
+              .long("pause-file")
+              .takes_value(true)
+              .value_name("FILE")
+              .help("Sets a sentinel file path to pause crunch. While the file exists no payouts are submitted, but crunch keeps running and subscribed to new blocks. Removing the file resumes the payouts from the next run (e.g. during a planned RPC migration)."))
+      .arg(
+        Arg::with_name("era-log-size")
+              .long("era-log-size")
+              .takes_value(true)
+              .help("Number of eras kept in the era log of the state file. When set, a record of each era processed (era, timestamp, validators paid, calls succeeded and failed) is kept, and eras with no record since the last run are reported on startup. A value of 0 disables the era log. [default: 0]"))
       .arg(
         Arg::with_name("maximum-calls")
               .long("maximum-calls")
               .takes_value(true)
-              .help("Maximum number of calls in a single batch. [default: 3]"))
+              .help("Maximum number of calls in a single batch. If not set, the default defined for each network is used (e.g. polkadot: 4, kusama: 4, westend: 8, paseo: 8)."))
+      .arg(
+        Arg::with_name("max-total-calls")
+              .long("max-total-calls")
+              .takes_value(true)
+              .help("Maximum number of calls across all batches in a single run. Once reached, no more batches are built and the remaining unclaimed eras are carried to the next run. A value of 0 means no limit. [default: 0]"))
+      .arg(
+        Arg::with_name("only-pages")
+              .long("only-pages")
+              .takes_value(true)
+              .value_name("ERA:PAGE")
+              .help("Claim only the given era and page combinations for each stash, bypassing the scan of unclaimed eras (e.g. 1500:0,1501:1). Useful to retry specific pages that previously failed."))
+      .arg(
+        Arg::with_name("batch-size-strategy")
+              .long("batch-size-strategy")
+              .takes_value(true)
+              .possible_values(&["fixed", "weight", "fee"])
+              .help("Sets how the number of calls in a single batch is defined. (e.g. the option 'fixed' sizes batches by 'maximum-calls'; the option 'weight' sizes batches to fit the maximum weight of a single extrinsic; the option 'fee' sizes batches to keep the estimated fee of each batch under 'max-batch-fee'). With 'weight' and 'fee', 'maximum-calls' is still the upper bound, so batches can only be smaller. [default: fixed]"))
+      .arg(
+        Arg::with_name("max-batch-fee")
+              .long("max-batch-fee")
+              .takes_value(true)
+              .help("Maximum estimated fee in PLANCKS for a single batch when 'batch-size-strategy' is 'fee'."))
+      .arg(
+        Arg::with_name("max-weight-fraction")
+              .long("max-weight-fraction")
+              .takes_value(true)
+              .help("Fraction of the maximum weight of a single extrinsic that a batch may fill when 'batch-size-strategy' is 'weight', between 0.1 and 1.0 (e.g. 0.75 fills batches up to 75% of the permitted weight, leaving room during congestion). [default: 1.0]"))
       .arg(
         Arg::with_name("debug")
           .long("debug")
           .help("Prints debug information verbosely."))
+      .arg(
+        Arg::with_name("no-color")
+          .long("no-color")
+          .help("Disables colors in the log output. Colors are also disabled when the NO_COLOR environment variable is set or when the log output is not a terminal (e.g. journald or docker logs)."))
       .arg(
         Arg::with_name("matrix-user")
           .long("matrix-user")
@@ -294,12 +1014,25 @@ fn get_config() -> Config {
           .help(
             "Disable notifications to matrix public rooms for 'crunch flakes'. (e.g. with this flag active 'crunch flakes' will not send messages/notifications about claimed or unclaimed staking rewards to any public 'Crunch Bot' room)",
           ))
+      .arg(
+        Arg::with_name("enable-public-matrix-room-redacted")
+          .long("enable-public-matrix-room-redacted")
+          .help(
+            "Send a redacted message to matrix public rooms for 'crunch flakes'. (e.g. with this flag active 'crunch flakes' will omit sensitive information like stash addresses or the signer account from the messages/notifications sent to the public 'Crunch Bot' room, the private room still receives the full message)",
+          ))
       .arg(
         Arg::with_name("disable-matrix-bot-display-name")
           .long("disable-matrix-bot-display-name")
           .help(
             "Disable matrix bot display name update for 'crunch flakes'. (e.g. with this flag active 'crunch flakes' will not change the matrix bot user display name)",
           ))
+      .arg(
+        Arg::with_name("matrix-alert-room")
+          .long("matrix-alert-room")
+          .takes_value(true)
+          .help(
+            "Matrix room alias or id where error alerts are sent instead of the private and public rooms. e.g. '#crunch-alerts:matrix.org' (e.g. with this option defined 'crunch flakes' keeps sending reports to the private or public 'Crunch Bot' rooms, while the on hold messages raised by errors are only sent to this room)",
+          ))
       .arg(
         Arg::with_name("short")
           .long("short")
@@ -313,6 +1046,26 @@ fn get_config() -> Config {
           .long("error-interval")
           .takes_value(true)
           .help("Interval value (in minutes) from which 'crunch' will restart again in case of a critical error."))
+      .arg(
+        Arg::with_name("min-run-interval")
+          .long("min-run-interval")
+          .takes_value(true)
+          .help("Minimum interval (in seconds) between consecutive era-triggered runs. An 'EraPaid' event received less than this interval after the previous run completed is skipped. [default: 0]"))
+      .arg(
+        Arg::with_name("keepalive-seconds")
+          .long("keepalive-seconds")
+          .takes_value(true)
+          .help("Interval (in seconds) without any finalized block received after which, in 'era' mode, a lightweight RPC call is sent to keep the connection alive and detect silent drops sooner. A value of 0 disables the keep-alive. [default: 0]"))
+      .arg(
+        Arg::with_name("min-random-wait")
+          .long("min-random-wait")
+          .takes_value(true)
+          .help("Minimum number of seconds 'crunch' waits, after the 'EraPaid' event, before trying to run the payouts. [default: 0]"))
+      .arg(
+        Arg::with_name("max-random-wait")
+          .long("max-random-wait")
+          .takes_value(true)
+          .help("Maximum number of seconds 'crunch' waits, after the 'EraPaid' event, before trying to run the payouts. The wait is randomly chosen between 'min-random-wait' and 'max-random-wait'. [default: 240]"))
       .arg(
         Arg::with_name("pool-ids")
           .long("pool-ids")
@@ -320,6 +1073,12 @@ fn get_config() -> Config {
           .help(
             "Nomination pool ids for which 'crunch' will try to fetch the validator stash addresses (e.g. poll_id_1, pool_id_2).",
           ))
+      .arg(
+        Arg::with_name("strict-pools")
+          .long("strict-pools")
+          .help(
+            "Abort the run if any of the nomination pool ids defined in 'pool-ids' does not exist on chain. By default a warning listing the missing pool ids is logged and the run continues.",
+          ))
       .arg(
         Arg::with_name("tx-tip")
           .long("tx-tip")
@@ -332,7 +1091,7 @@ fn get_config() -> Config {
           .long("tx-mortal-period")
           .takes_value(true)
           .help(
-            "Define the number of blocks the transaction is mortal for.",
+            "Define the number of blocks the transaction is mortal for. Valid values range from 4 blocks to the number of block hashes kept by the chain (e.g. 4096 blocks on Polkadot), larger values are clamped to the chain limit. A value of 0 means immortal transactions. [default: 0]",
           ))
       .arg(
         Arg::with_name("enable-pool-compound-threshold")
@@ -341,6 +1100,13 @@ fn get_config() -> Config {
           .help(
             "Define minimum pending rewards threshold in PLANCKS. (e.g. Only pending rewards above the threshold are include in the auto-compound batch)",
           ))
+      .arg(
+        Arg::with_name("pool-compound-min-interval-eras")
+          .long("pool-compound-min-interval-eras")
+          .takes_value(true)
+          .help(
+            "Minimum number of eras between two compounds of the same pool member, tracked in the state file. Members compounded less than N eras ago are skipped, so that rewards accumulate to a more efficient size before being bonded. A value of 0 means no minimum interval. [default: 0]",
+          ))
       .arg(
         Arg::with_name("enable-pool-members-compound")
           .long("enable-pool-members-compound")
@@ -353,12 +1119,32 @@ fn get_config() -> Config {
           .help(
             "Allow 'crunch' to compound rewards for the pool operator member that belongs to the pools previously selected by '--pool-ids' option. Note that the operator member account have to have their permissions set as PermissionlessCompound or PermissionlessAll.",
           ))
+      .arg(
+        Arg::with_name("operator-compound-without-permission")
+          .long("operator-compound-without-permission")
+          .help(
+            "Allow 'crunch' to compound the pool operator rewards when the signer account is the pool depositor, even if the depositor has no permissionless claim permission set. A member can always compound its own rewards, so no permission is needed in this case.",
+          ))
+      .arg(
+        Arg::with_name("enable-pool-operator-membership-check")
+          .long("enable-pool-operator-membership-check")
+          .help(
+            "Verify that the pool operator (depositor) is still bonded as a member of the pool before compounding its rewards. Operators that are no longer members of the pool are skipped.",
+          ))
       .arg(
         Arg::with_name("enable-pool-active-nominees-payout")
           .long("enable-pool-active-nominees-payout")
           .help(
             "Enable payouts only for ACTIVE nominees assigned to the Nomination Pools defined in 'pool-ids'. (e.g. with this flag active 'crunch' will try to trigger payouts only for the ACTIVE nominees and not all).",
           ))
+      .arg(
+        Arg::with_name("pool-nominee-era-offset")
+          .long("pool-nominee-era-offset")
+          .takes_value(true)
+          .value_name("ERAS")
+          .help(
+            "Number of eras before the active era in which the pool nominees are checked as ACTIVE, when 'enable-pool-active-nominees-payout' is set. A value of 0 checks the nominees active in the current era, which suits pools that change their nominations frequently. [default: 1]",
+          ))
       .arg(
         Arg::with_name("enable-pool-all-nominees-payout")
           .long("enable-pool-all-nominees-payout")
@@ -371,9 +1157,110 @@ fn get_config() -> Config {
           .help(
             "Allow 'crunch' to fetch grades for every stash from ONE-T API.",
           ))
+      .arg(
+        Arg::with_name("report-template")
+          .long("report-template")
+          .takes_value(true)
+          .value_name("FILE")
+          .help(
+            "Sets a custom report template file path. Placeholders like {{network}}, {{era}}, {{validators}}, {{calls_succeeded}}, {{total_rewarded}} or {{body}} (the built-in report) are replaced by the respective values in the message sent to matrix.",
+          ))
+      .arg(
+        Arg::with_name("report-sort")
+          .long("report-sort")
+          .takes_value(true)
+          .possible_values(&["name", "reward", "unclaimed"])
+          .help(
+            "Sets how validators are sorted in the report. (e.g. the option 'name' sorts validators by identity name; the option 'reward' shows the validators with the largest reward first; the option 'unclaimed' shows the validators with the most unclaimed eras first) [default: name]",
+          ))
+      .arg(
+        Arg::with_name("report-locale")
+          .long("report-locale")
+          .takes_value(true)
+          .value_name("LOCALE")
+          .help(
+            "Locale (e.g. en_US, de_DE, fr_FR) used to format the decimal and thousands separators of the amounts shown in the report. If not set, amounts are shown with no thousands separator and a dot as decimal separator (e.g. 1234.5678).",
+          ))
+      .arg(
+        Arg::with_name("report-timezone")
+          .long("report-timezone")
+          .takes_value(true)
+          .value_name("TIMEZONE")
+          .help(
+            "IANA time zone name (e.g. Europe/Lisbon, America/New_York) used to format the timestamps shown in the report. [default: UTC]",
+          ))
+      .arg(
+        Arg::with_name("identity-max-len")
+          .long("identity-max-len")
+          .takes_value(true)
+          .help(
+            "Maximum number of characters of the validator identities shown in the report, longer identities are truncated. A value of 0 means no limit. [default: 0]",
+          ))
+      .arg(
+        Arg::with_name("strip-emoji")
+          .long("strip-emoji")
+          .help(
+            "Remove emoji from the validator identities shown in the report.",
+          ))
+      .arg(
+        Arg::with_name("report-minimal-on-success")
+          .long("report-minimal-on-success")
+          .help(
+            "Collapse the report into a single success line when all payouts succeeded and there are no warnings. The full report is still sent when there are failures or warnings, and is always logged.",
+          ))
+      .arg(
+        Arg::with_name("report-include-controller")
+          .long("report-include-controller")
+          .help(
+            "Show the controller account of each validator in the report. Note: the controller is only shown in the private room.",
+          ))
+      .arg(
+        Arg::with_name("report-compare-previous-run")
+          .long("report-compare-previous-run")
+          .help(
+            "Compare each run with the previous one and show the changes in the report (calls succeeded, validators paid, new warnings and validators that newly fell behind). The previous run is persisted in the state file defined by 'state-path'.",
+          ))
+      .arg(
+        Arg::with_name("report-validator-url-template")
+          .long("report-validator-url-template")
+          .takes_value(true)
+          .value_name("URL")
+          .help(
+            "Explorer URL template of the validator links shown in the report, with the placeholders {network}, {stash}, {block} and {extrinsic} (e.g. https://{network}.statescan.io/#/accounts/{stash}). [default: https://{network}.subscan.io/validator/{stash}]",
+          ))
+      .arg(
+        Arg::with_name("report-extrinsic-url-template")
+          .long("report-extrinsic-url-template")
+          .takes_value(true)
+          .value_name("URL")
+          .help(
+            "Explorer URL template of the extrinsic links shown in the report, with the placeholders {network}, {stash}, {block} and {extrinsic} (e.g. https://{network}.statescan.io/#/extrinsics/{extrinsic}). [default: https://{network}.subscan.io/extrinsic/{extrinsic}]",
+          ))
+      .arg(
+        Arg::with_name("high-commission-warn")
+          .long("high-commission-warn")
+          .takes_value(true)
+          .value_name("PERCENT")
+          .help(
+            "Commission percentage from which a note is added to the report, since nominators of the validator receive little or no rewards. [default: 100]",
+          ))
+      .arg(
+        Arg::with_name("report-split-pools")
+          .long("report-split-pools")
+          .help(
+            "Send the nomination pools report as its own message, separate from the validators payout report.",
+          ))
+      .arg(
+        Arg::with_name("report-currency")
+          .long("report-currency")
+          .takes_value(true)
+          .value_name("CURRENCY")
+          .help(
+            "Fiat currency (e.g. usd, eur) in which reward amounts are also shown in the report. The token price is fetched once per run from the price API defined by CRUNCH_PRICE_API_URL.",
+          ))
     )
     .subcommand(SubCommand::with_name("rewards")
-      .about("Claim staking rewards for unclaimed eras once a day or four times a day [default subcommand]")
+      .about("Claim staking rewards for unclaimed eras once a day or four times a day, with a plain report style where every emoji is removed [default subcommand]")
       .arg(
         Arg::with_name("MODE")
             .index(1)
@@ -389,28 +1276,217 @@ fn get_config() -> Config {
           .takes_value(true)
           .value_name("FILE")
           .help(
-            "Sets a custom seed file path. The seed file contains the private seed phrase to Sign the extrinsic payout call.",
+            "Sets a custom seed file path. The seed file contains the private seed phrase to Sign the extrinsic payout call. An sr25519 JSON keystore file exported from polkadot.js is also supported, decrypted with the password defined by CRUNCH_KEYSTORE_PASSWORD.",
+          ))
+      .arg(
+        Arg::with_name("external-signer-cmd")
+          .long("external-signer-cmd")
+          .takes_value(true)
+          .value_name("CMD")
+          .help(
+            "Sets an external command to Sign the extrinsic payout call instead of using the seed file. The command receives the signer payload hex encoded on stdin and must write back the sr25519 signature hex encoded on stdout. Requires 'external-signer-account'.",
+          ))
+      .arg(
+        Arg::with_name("external-signer-account")
+          .long("external-signer-account")
+          .takes_value(true)
+          .help(
+            "The account (SS58 address) of the external signer defined by 'external-signer-cmd'.",
+          ))
+      .arg(
+        Arg::with_name("external-signer-timeout")
+          .long("external-signer-timeout")
+          .takes_value(true)
+          .help(
+            "Maximum number of seconds to wait for the external signer command to return the signature.",
+          ))
+      .arg(
+        Arg::with_name("funding-seed-path")
+          .long("funding-seed-path")
+          .takes_value(true)
+          .value_name("FILE")
+          .help(
+            "Sets the seed file path of a funding account used to top up the signer account. When the signer free balance drops below 'signer-topup-threshold', 'signer-topup-amount' is transferred from the funding account to the signer before running payouts.",
+          ))
+      .arg(
+        Arg::with_name("signer-topup-threshold")
+          .long("signer-topup-threshold")
+          .takes_value(true)
+          .help(
+            "Signer free balance in PLANCKS below which the signer account is topped up from the funding account defined by 'funding-seed-path'. [default: 0]",
+          ))
+      .arg(
+        Arg::with_name("signer-topup-amount")
+          .long("signer-topup-amount")
+          .takes_value(true)
+          .help(
+            "Amount in PLANCKS transferred from the funding account to the signer account on each top-up. A value of 0 disables the top-up. [default: 0]",
           ))
       .arg(
         Arg::with_name("maximum-payouts")
           .short("m")
           .long("maximum-payouts")
           .takes_value(true)
-          .help("Maximum number of unclaimed eras for which an extrinsic payout will be submitted. (e.g. a value of 4 means that if there are unclaimed eras in the last 84 the maximum unclaimed payout calls for each stash address will be 4)."))
+          .help("Maximum number of unclaimed eras for which an extrinsic payout will be submitted. (e.g. a value of 4 means that if there are unclaimed eras in the last 84 the maximum unclaimed payout calls for each stash address will be 4). A value of 0 means unlimited, all unclaimed eras found within 'maximum-history-eras' will be claimed. [default: 4]"))
+      .arg(
+        Arg::with_name("maximum-payouts-overrides")
+          .long("maximum-payouts-overrides")
+          .takes_value(true)
+          .help("Maximum number of unclaimed eras for which an extrinsic payout will be submitted for specific stash addresses, overriding 'maximum-payouts' (e.g. stash_1:0,stash_2:8). A value of 0 means unlimited. Stashes not listed use 'maximum-payouts'."))
       .arg(
         Arg::with_name("maximum-history-eras")
               .long("maximum-history-eras")
               .takes_value(true)
               .help("Maximum number of history eras for which crunch will look for unclaimed rewards. The maximum value supported is the one defined by the constant history_depth - usually 84 - (e.g. a value of 4 means that crunch will only check in latest 4 eras if there are any unclaimed rewards for each stash address). [default: 4]"))
+      .arg(
+        Arg::with_name("skip-recent-eras")
+              .long("skip-recent-eras")
+              .takes_value(true)
+              .help("Number of the most recent eras excluded from the unclaimed rewards scan, for chains where the rewards of the latest eras are not yet available to be claimed (e.g. a value of 1 means that the era that just ended is only claimed in the next run). [default: 0]"))
+      .arg(
+        Arg::with_name("respect-max-eras-in-verbose")
+              .long("respect-max-eras-in-verbose")
+              .help("Apply 'maximum-history-eras' also when 'short' or 'medium' flags are not used. (e.g. with this flag active 'crunch' will not look for unclaimed rewards in all history eras even if running with the most verbose messages/notifications)"))
+      .arg(
+        Arg::with_name("era-chunk-size")
+              .long("era-chunk-size")
+              .takes_value(true)
+              .help("Number of history eras scanned for unclaimed rewards before submitting the respective payouts. Payouts are submitted chunk by chunk, from the oldest to the latest eras, instead of only after all history eras have been scanned (e.g. a value of 7 means that crunch will look for unclaimed rewards in 7 eras at a time). A value of 0 disables chunked scanning. [default: 0]"))
+      .arg(
+        Arg::with_name("latest-era-only")
+              .long("latest-era-only")
+              .help("Only look for unclaimed rewards in the latest completed era (active era - 1), skipping the history eras entirely. This is the fastest mode for operators running 'crunch' every era."))
+      .arg(
+        Arg::with_name("era-scan-concurrency")
+              .long("era-scan-concurrency")
+              .takes_value(true)
+              .help("Maximum number of history eras scanned in parallel for each stash when looking for unclaimed rewards. Higher values speed up the scan against high-latency RPC endpoints. [default: 1]"))
+      .arg(
+        Arg::with_name("enable-skip-inactive-eras")
+              .long("enable-skip-inactive-eras")
+              .help("Skip history eras where the stash was not in the active set (had no exposure) before looking up its claimed and unclaimed pages. This avoids unnecessary storage requests when scanning for unclaimed rewards."))
+      .arg(
+        Arg::with_name("enable-skip-legacy-claimed")
+              .long("enable-skip-legacy-claimed")
+              .help("Skip the staking ledger lookup of the legacy claimed rewards and look up the paged claimed rewards only. This avoids one storage request per stash on chains that no longer use legacy claimed rewards."))
+      .arg(
+        Arg::with_name("enable-skip-zero-reward-eras")
+              .long("enable-skip-zero-reward-eras")
+              .help("Skip history eras where the stash earned no reward points, and hence no rewards, instead of claiming them. This avoids paying fees for payouts with nothing to claim."))
+      .arg(
+        Arg::with_name("enable-confirm-rewards")
+              .long("enable-confirm-rewards")
+              .help("Confirm that the rewards of each payout were actually credited, by cross checking the 'Rewarded' amounts with the balance deposits in the same batch. Any discrepancy (e.g. rewards restaked to a pool or sent to no destination) is flagged in the report."))
+      .arg(
+        Arg::with_name("enable-continue-on-batch-failure")
+              .long("enable-continue-on-batch-failure")
+              .help("Continue the run when a batch fails with ExtrinsicFailed, instead of aborting it. The failed batch is logged and shown in the report, and the remaining batches are still submitted."))
+      .arg(
+        Arg::with_name("dry-run")
+              .long("dry-run")
+              .help("Simulate the payouts without signing or submitting any extrinsic. The call data and the estimated fee of every batch that would be submitted are logged, and the report is still generated with a DRY RUN banner. Pool members compound batches are logged the same way and signer top-ups are skipped. Note that the 'weight' and 'fee' batch size strategies still sign batches locally to estimate them, but never submit them."))
+      .arg(
+        Arg::with_name("enable-log-all-events")
+              .long("enable-log-all-events")
+              .help("Log every event of the blocks where the payout and compound batches are included (pallet, variant and fields), not only the events handled by crunch. Events are logged at debug level, so it should be used together with --debug."))
+      .arg(
+        Arg::with_name("enable-risk-check")
+              .long("enable-risk-check")
+              .help("Check if each validator has been chilled or has a slash recorded that may still be pending to be applied, and add a warning to the report when a validator is at risk."))
+      .arg(
+        Arg::with_name("enable-export")
+              .long("enable-export")
+              .help("Export the data of each run (network, summary, validators, claimed and unclaimed eras and payouts) as a single JSON line appended to the export file."))
+      .arg(
+        Arg::with_name("export-path")
+              .long("export-path")
+              .takes_value(true)
+              .value_name("FILE")
+              .help("Sets a custom export file path. [default: .crunch.export.jsonl]"))
+      .arg(
+        Arg::with_name("export-raw-points")
+              .long("export-raw-points")
+              .help("Include the reward points of every validator in the era of each payout in the export, so that the points distribution can be analysed. Note: this considerably increases the size of each export line."))
+      .arg(
+        Arg::with_name("export-nominator-detail")
+              .long("export-nominator-detail")
+              .help("Include the account and amount of every nominator rewarded in each payout in the export, so that rewards can be accounted per nominator. Note: nominator detail is only exported, never included in the report messages."))
+      .arg(
+        Arg::with_name("enable-metrics")
+              .long("enable-metrics")
+              .help("Serve run metrics (payout calls, fees paid, last run timestamp, last era processed and signer balance) labeled by network, in the OpenMetrics text format at 'http://0.0.0.0:<metrics-port>/metrics'."))
+      .arg(
+        Arg::with_name("metrics-port")
+              .long("metrics-port")
+              .takes_value(true)
+              .help("Sets the port of the metrics server. [default: 9616]"))
+      .arg(
+        Arg::with_name("dashboard-port")
+              .long("dashboard-port")
+              .takes_value(true)
+              .help("Serve a status page at 'http://0.0.0.0:<dashboard-port>' showing the connected chain, active era, signer balance, last run summary, pending unclaimed pages per stash and recent errors. By default the dashboard is disabled. [default: 0]"))
+      .arg(
+        Arg::with_name("max-stashes-per-run")
+              .long("max-stashes-per-run")
+              .takes_value(true)
+              .help("Maximum number of stashes processed in a single run. When set, each run processes the next window of stashes in rotation, so that across successive runs all stashes are eventually covered. The rotation cursor is persisted in the state file. A value of 0 processes all stashes in every run. [default: 0]"))
+      .arg(
+        Arg::with_name("state-path")
+              .long("state-path")
+              .takes_value(true)
+              .value_name("FILE")
+              .help("Sets a custom state file path. The state file is used to persist data between runs (e.g. the stashes rotation cursor). [default: .crunch.state.json]"))
+      .arg(
+        Arg::with_name("pause-file")
+              .long("pause-file")
+              .takes_value(true)
+              .value_name("FILE")
+              .help("Sets a sentinel file path to pause crunch. While the file exists no payouts are submitted, but crunch keeps running and subscribed to new blocks. Removing the file resumes the payouts from the next run (e.g. during a planned RPC migration)."))
+      .arg(
+        Arg::with_name("era-log-size")
+              .long("era-log-size")
+              .takes_value(true)
+              .help("Number of eras kept in the era log of the state file. When set, a record of each era processed (era, timestamp, validators paid, calls succeeded and failed) is kept, and eras with no record since the last run are reported on startup. A value of 0 disables the era log. [default: 0]"))
       .arg(
         Arg::with_name("maximum-calls")
               .long("maximum-calls")
               .takes_value(true)
-              .help("Maximum number of calls in a single batch. [default: 8]"))
+              .help("Maximum number of calls in a single batch. If not set, the default defined for each network is used (e.g. polkadot: 4, kusama: 4, westend: 8, paseo: 8)."))
+      .arg(
+        Arg::with_name("max-total-calls")
+              .long("max-total-calls")
+              .takes_value(true)
+              .help("Maximum number of calls across all batches in a single run. Once reached, no more batches are built and the remaining unclaimed eras are carried to the next run. A value of 0 means no limit. [default: 0]"))
+      .arg(
+        Arg::with_name("only-pages")
+              .long("only-pages")
+              .takes_value(true)
+              .value_name("ERA:PAGE")
+              .help("Claim only the given era and page combinations for each stash, bypassing the scan of unclaimed eras (e.g. 1500:0,1501:1). Useful to retry specific pages that previously failed."))
+      .arg(
+        Arg::with_name("batch-size-strategy")
+              .long("batch-size-strategy")
+              .takes_value(true)
+              .possible_values(&["fixed", "weight", "fee"])
+              .help("Sets how the number of calls in a single batch is defined. (e.g. the option 'fixed' sizes batches by 'maximum-calls'; the option 'weight' sizes batches to fit the maximum weight of a single extrinsic; the option 'fee' sizes batches to keep the estimated fee of each batch under 'max-batch-fee'). With 'weight' and 'fee', 'maximum-calls' is still the upper bound, so batches can only be smaller. [default: fixed]"))
+      .arg(
+        Arg::with_name("max-batch-fee")
+              .long("max-batch-fee")
+              .takes_value(true)
+              .help("Maximum estimated fee in PLANCKS for a single batch when 'batch-size-strategy' is 'fee'."))
+      .arg(
+        Arg::with_name("max-weight-fraction")
+              .long("max-weight-fraction")
+              .takes_value(true)
+              .help("Fraction of the maximum weight of a single extrinsic that a batch may fill when 'batch-size-strategy' is 'weight', between 0.1 and 1.0 (e.g. 0.75 fills batches up to 75% of the permitted weight, leaving room during congestion). [default: 1.0]"))
       .arg(
         Arg::with_name("debug")
           .long("debug")
           .help("Prints debug information verbosely."))
+      .arg(
+        Arg::with_name("no-color")
+          .long("no-color")
+          .help("Disables colors in the log output. Colors are also disabled when the NO_COLOR environment variable is set or when the log output is not a terminal (e.g. journald or docker logs)."))
       .arg(
         Arg::with_name("matrix-user")
           .long("matrix-user")
@@ -438,12 +1514,25 @@ fn get_config() -> Config {
           .help(
             "Disable notifications to matrix public rooms for 'crunch rewards'. (e.g. with this flag active 'crunch rewards' will not send messages/notifications about claimed or unclaimed staking rewards to any public 'Crunch Bot' room)",
           ))
+      .arg(
+        Arg::with_name("enable-public-matrix-room-redacted")
+          .long("enable-public-matrix-room-redacted")
+          .help(
+            "Send a redacted message to matrix public rooms for 'crunch rewards'. (e.g. with this flag active 'crunch rewards' will omit sensitive information like stash addresses or the signer account from the messages/notifications sent to the public 'Crunch Bot' room, the private room still receives the full message)",
+          ))
       .arg(
         Arg::with_name("disable-matrix-bot-display-name")
           .long("disable-matrix-bot-display-name")
           .help(
             "Disable matrix bot display name update for 'crunch rewards'. (e.g. with this flag active 'crunch rewards' will not change the matrix bot user display name)",
           ))
+      .arg(
+        Arg::with_name("matrix-alert-room")
+          .long("matrix-alert-room")
+          .takes_value(true)
+          .help(
+            "Matrix room alias or id where error alerts are sent instead of the private and public rooms. e.g. '#crunch-alerts:matrix.org' (e.g. with this option defined 'crunch rewards' keeps sending reports to the private or public 'Crunch Bot' rooms, while the on hold messages raised by errors are only sent to this room)",
+          ))
       .arg(
         Arg::with_name("short")
           .long("short")
@@ -458,66 +1547,251 @@ fn get_config() -> Config {
           .takes_value(true)
           .help("Interval value (in minutes) from which 'crunch' will restart again in case of a critical error."))
       .arg(
-        Arg::with_name("pool-ids")
-          .long("pool-ids")
+        Arg::with_name("min-run-interval")
+          .long("min-run-interval")
+          .takes_value(true)
+          .help("Minimum interval (in seconds) between consecutive era-triggered runs. An 'EraPaid' event received less than this interval after the previous run completed is skipped. [default: 0]"))
+      .arg(
+        Arg::with_name("keepalive-seconds")
+          .long("keepalive-seconds")
+          .takes_value(true)
+          .help("Interval (in seconds) without any finalized block received after which, in 'era' mode, a lightweight RPC call is sent to keep the connection alive and detect silent drops sooner. A value of 0 disables the keep-alive. [default: 0]"))
+      .arg(
+        Arg::with_name("min-random-wait")
+          .long("min-random-wait")
+          .takes_value(true)
+          .help("Minimum number of seconds 'crunch' waits, after the 'EraPaid' event, before trying to run the payouts. [default: 0]"))
+      .arg(
+        Arg::with_name("max-random-wait")
+          .long("max-random-wait")
+          .takes_value(true)
+          .help("Maximum number of seconds 'crunch' waits, after the 'EraPaid' event, before trying to run the payouts. The wait is randomly chosen between 'min-random-wait' and 'max-random-wait'. [default: 240]"))
+      .arg(
+        Arg::with_name("pool-ids")
+          .long("pool-ids")
+          .takes_value(true)
+          .help(
+            "Nomination pool ids for which 'crunch' will try to fetch the validator stash addresses (e.g. poll_id_1, pool_id_2).",
+          ))
+      .arg(
+        Arg::with_name("strict-pools")
+          .long("strict-pools")
+          .help(
+            "Abort the run if any of the nomination pool ids defined in 'pool-ids' does not exist on chain. By default a warning listing the missing pool ids is logged and the run continues.",
+          ))
+      .arg(
+        Arg::with_name("tx-tip")
+          .long("tx-tip")
+          .takes_value(true)
+          .help(
+            "Define a tip in PLANCKS for the block author.",
+          ))
+      .arg(
+        Arg::with_name("tx-mortal-period")
+          .long("tx-mortal-period")
+          .takes_value(true)
+          .help(
+            "Define the number of blocks the transaction is mortal for. Valid values range from 4 blocks to the number of block hashes kept by the chain (e.g. 4096 blocks on Polkadot), larger values are clamped to the chain limit. A value of 0 means immortal transactions. [default: 0]",
+          ))
+      .arg(
+        Arg::with_name("enable-pool-compound-threshold")
+          .long("enable-pool-compound-threshold")
+          .takes_value(true)
+          .help(
+            "Define minimum pending rewards threshold in PLANCKS. (e.g. Only pending rewards above the threshold are include in the auto-compound batch)",
+          ))
+      .arg(
+        Arg::with_name("pool-compound-min-interval-eras")
+          .long("pool-compound-min-interval-eras")
+          .takes_value(true)
+          .help(
+            "Minimum number of eras between two compounds of the same pool member, tracked in the state file. Members compounded less than N eras ago are skipped, so that rewards accumulate to a more efficient size before being bonded. A value of 0 means no minimum interval. [default: 0]",
+          ))
+      .arg(
+        Arg::with_name("enable-pool-members-compound")
+          .long("enable-pool-members-compound")
+          .help(
+            "Allow 'crunch' to compound rewards for every member that belongs to the pools previously selected by '--pool-ids' option. Note that members have to have their permissions set as PermissionlessCompound or PermissionlessAll.",
+          ))
+      .arg(
+        Arg::with_name("enable-pool-only-operator-compound")
+          .long("enable-pool-only-operator-compound")
+          .help(
+            "Allow 'crunch' to compound rewards for the pool operator member that belongs to the pools previously selected by '--pool-ids' option. Note that the operator member account have to have their permissions set as PermissionlessCompound or PermissionlessAll.",
+          ))
+      .arg(
+        Arg::with_name("operator-compound-without-permission")
+          .long("operator-compound-without-permission")
+          .help(
+            "Allow 'crunch' to compound the pool operator rewards when the signer account is the pool depositor, even if the depositor has no permissionless claim permission set. A member can always compound its own rewards, so no permission is needed in this case.",
+          ))
+      .arg(
+        Arg::with_name("enable-pool-operator-membership-check")
+          .long("enable-pool-operator-membership-check")
+          .help(
+            "Verify that the pool operator (depositor) is still bonded as a member of the pool before compounding its rewards. Operators that are no longer members of the pool are skipped.",
+          ))
+      .arg(
+        Arg::with_name("enable-pool-active-nominees-payout")
+          .long("enable-pool-active-nominees-payout")
+          .help(
+            "Enable payouts only for ACTIVE nominees assigned to the Nomination Pools defined in 'pool-ids'. (e.g. with this flag active 'crunch' will try to trigger payouts only for the ACTIVE nominees and not all).",
+          ))
+      .arg(
+        Arg::with_name("pool-nominee-era-offset")
+          .long("pool-nominee-era-offset")
+          .takes_value(true)
+          .value_name("ERAS")
+          .help(
+            "Number of eras before the active era in which the pool nominees are checked as ACTIVE, when 'enable-pool-active-nominees-payout' is set. A value of 0 checks the nominees active in the current era, which suits pools that change their nominations frequently. [default: 1]",
+          ))
+      .arg(
+        Arg::with_name("enable-pool-all-nominees-payout")
+          .long("enable-pool-all-nominees-payout")
+          .help(
+            "Enable payouts for ALL the nominees assigned to the Nomination Pools defined in 'pool-ids'. (e.g. with this flag active 'crunch' will try to trigger payouts for ALL nominees and not only the active ones - the ones the stake of the Nomination Pool was allocated).",
+          ))
+      .arg(
+        Arg::with_name("enable-onet-api")
+          .long("enable-onet-api")
+          .help(
+            "Allow 'crunch' to fetch grades for every stash from ONE-T API.",
+          ))
+      .arg(
+        Arg::with_name("report-template")
+          .long("report-template")
+          .takes_value(true)
+          .value_name("FILE")
+          .help(
+            "Sets a custom report template file path. Placeholders like {{network}}, {{era}}, {{validators}}, {{calls_succeeded}}, {{total_rewarded}} or {{body}} (the built-in report) are replaced by the respective values in the message sent to matrix.",
+          ))
+      .arg(
+        Arg::with_name("report-sort")
+          .long("report-sort")
+          .takes_value(true)
+          .possible_values(&["name", "reward", "unclaimed"])
+          .help(
+            "Sets how validators are sorted in the report. (e.g. the option 'name' sorts validators by identity name; the option 'reward' shows the validators with the largest reward first; the option 'unclaimed' shows the validators with the most unclaimed eras first) [default: name]",
+          ))
+      .arg(
+        Arg::with_name("report-locale")
+          .long("report-locale")
+          .takes_value(true)
+          .value_name("LOCALE")
+          .help(
+            "Locale (e.g. en_US, de_DE, fr_FR) used to format the decimal and thousands separators of the amounts shown in the report. If not set, amounts are shown with no thousands separator and a dot as decimal separator (e.g. 1234.5678).",
+          ))
+      .arg(
+        Arg::with_name("report-timezone")
+          .long("report-timezone")
           .takes_value(true)
+          .value_name("TIMEZONE")
           .help(
-            "Nomination pool ids for which 'crunch' will try to fetch the validator stash addresses (e.g. poll_id_1, pool_id_2).",
+            "IANA time zone name (e.g. Europe/Lisbon, America/New_York) used to format the timestamps shown in the report. [default: UTC]",
           ))
       .arg(
-        Arg::with_name("tx-tip")
-          .long("tx-tip")
+        Arg::with_name("identity-max-len")
+          .long("identity-max-len")
           .takes_value(true)
           .help(
-            "Define a tip in PLANCKS for the block author.",
+            "Maximum number of characters of the validator identities shown in the report, longer identities are truncated. A value of 0 means no limit. [default: 0]",
           ))
       .arg(
-        Arg::with_name("tx-mortal-period")
-          .long("tx-mortal-period")
+        Arg::with_name("strip-emoji")
+          .long("strip-emoji")
+          .help(
+            "Remove emoji from the validator identities shown in the report.",
+          ))
+      .arg(
+        Arg::with_name("report-minimal-on-success")
+          .long("report-minimal-on-success")
+          .help(
+            "Collapse the report into a single success line when all payouts succeeded and there are no warnings. The full report is still sent when there are failures or warnings, and is always logged.",
+          ))
+      .arg(
+        Arg::with_name("report-include-controller")
+          .long("report-include-controller")
+          .help(
+            "Show the controller account of each validator in the report. Note: the controller is only shown in the private room.",
+          ))
+      .arg(
+        Arg::with_name("report-compare-previous-run")
+          .long("report-compare-previous-run")
+          .help(
+            "Compare each run with the previous one and show the changes in the report (calls succeeded, validators paid, new warnings and validators that newly fell behind). The previous run is persisted in the state file defined by 'state-path'.",
+          ))
+      .arg(
+        Arg::with_name("report-validator-url-template")
+          .long("report-validator-url-template")
           .takes_value(true)
+          .value_name("URL")
           .help(
-            "Define the number of blocks the transaction is mortal for (default is 64 blocks)",
+            "Explorer URL template of the validator links shown in the report, with the placeholders {network}, {stash}, {block} and {extrinsic} (e.g. https://{network}.statescan.io/#/accounts/{stash}). [default: https://{network}.subscan.io/validator/{stash}]",
           ))
       .arg(
-        Arg::with_name("enable-pool-compound-threshold")
-          .long("enable-pool-compound-threshold")
+        Arg::with_name("report-extrinsic-url-template")
+          .long("report-extrinsic-url-template")
           .takes_value(true)
+          .value_name("URL")
           .help(
-            "Define minimum pending rewards threshold in PLANCKS. (e.g. Only pending rewards above the threshold are include in the auto-compound batch)",
+            "Explorer URL template of the extrinsic links shown in the report, with the placeholders {network}, {stash}, {block} and {extrinsic} (e.g. https://{network}.statescan.io/#/extrinsics/{extrinsic}). [default: https://{network}.subscan.io/extrinsic/{extrinsic}]",
           ))
       .arg(
-        Arg::with_name("enable-pool-members-compound")
-          .long("enable-pool-members-compound")
+        Arg::with_name("high-commission-warn")
+          .long("high-commission-warn")
+          .takes_value(true)
+          .value_name("PERCENT")
           .help(
-            "Allow 'crunch' to compound rewards for every member that belongs to the pools previously selected by '--pool-ids' option. Note that members have to have their permissions set as PermissionlessCompound or PermissionlessAll.",
+            "Commission percentage from which a note is added to the report, since nominators of the validator receive little or no rewards. [default: 100]",
           ))
       .arg(
-        Arg::with_name("enable-pool-only-operator-compound")
-          .long("enable-pool-only-operator-compound")
+        Arg::with_name("report-split-pools")
+          .long("report-split-pools")
           .help(
-            "Allow 'crunch' to compound rewards for the pool operator member that belongs to the pools previously selected by '--pool-ids' option. Note that the operator member account have to have their permissions set as PermissionlessCompound or PermissionlessAll.",
+            "Send the nomination pools report as its own message, separate from the validators payout report.",
           ))
       .arg(
-        Arg::with_name("enable-pool-active-nominees-payout")
-          .long("enable-pool-active-nominees-payout")
+        Arg::with_name("report-currency")
+          .long("report-currency")
+          .takes_value(true)
+          .value_name("CURRENCY")
           .help(
-            "Enable payouts only for ACTIVE nominees assigned to the Nomination Pools defined in 'pool-ids'. (e.g. with this flag active 'crunch' will try to trigger payouts only for the ACTIVE nominees and not all).",
+            "Fiat currency (e.g. usd, eur) in which reward amounts are also shown in the report. The token price is fetched once per run from the price API defined by CRUNCH_PRICE_API_URL.",
           ))
+    )
+    .subcommand(SubCommand::with_name("view")
+      .about("Inspect staking rewards for the given stashes and display claimed and unclaimed eras. When CRUNCH_POOL_IDS is set, the pending rewards, claimable commission and members of each pool are also displayed.")
       .arg(
-        Arg::with_name("enable-pool-all-nominees-payout")
-          .long("enable-pool-all-nominees-payout")
+        Arg::with_name("at-block")
+          .long("at-block")
+          .takes_value(true)
+          .value_name("N|HASH")
           .help(
-            "Enable payouts for ALL the nominees assigned to the Nomination Pools defined in 'pool-ids'. (e.g. with this flag active 'crunch' will try to trigger payouts for ALL nominees and not only the active ones - the ones the stake of the Nomination Pool was allocated).",
+            "Inspect staking rewards as they were at the given block number or block hash, instead of the latest block.",
           ))
       .arg(
-        Arg::with_name("enable-onet-api")
-          .long("enable-onet-api")
+        Arg::with_name("json")
+          .long("json")
           .help(
-            "Allow 'crunch' to fetch grades for every stash from ONE-T API.",
+            "Print a final JSON summary line with the total stashes inspected, the total claimed and unclaimed pages and a breakdown per stash, so that the output can be consumed by scripts.",
           ))
     )
-    .subcommand(SubCommand::with_name("view")
-      .about("Inspect staking rewards for the given stashes and display claimed and unclaimed eras.")
+    .subcommand(SubCommand::with_name("pending")
+      .about("Check if there are unclaimed rewards for the given stashes without submitting any payout. The number of unclaimed pages is printed and crunch exits with code 0 if nothing is unclaimed, 1 if there are unclaimed pages or 2 if the check could not be completed.")
+    )
+    .subcommand(SubCommand::with_name("notify-test")
+      .about("Send a test message through every configured notification channel and report the success or failure of each one. For Matrix, the bot login and the rooms join are verified and the message is sent to the private room only.")
+    )
+    .subcommand(SubCommand::with_name("dump-metadata")
+      .about("Fetch the runtime metadata of the connected node and write it SCALE encoded to a file, so that it can be compared with the metadata embedded in crunch (e.g. metadata/polkadot_metadata_small.scale) when payouts fail to decode after a runtime upgrade.")
+      .arg(
+        Arg::with_name("output")
+          .long("output")
+          .takes_value(true)
+          .value_name("FILE")
+          .help(
+            "Path of the file where the runtime metadata is written. [default: <network>_metadata.scale]",
+          ))
     )
     .arg(
       Arg::with_name("stashes")
@@ -534,6 +1808,13 @@ fn get_config() -> Config {
         .help(
           "Remote stashes endpoint for which 'crunch' will try to fetch the validator stash addresses (e.g. https://raw.githubusercontent.com/turboflakes/crunch/main/.remote.stashes.example).",
         ))
+    .arg(
+      Arg::with_name("stash-sources")
+        .long("stash-sources")
+        .takes_value(true)
+        .help(
+          "Sources from which the validator stash addresses are collected, so that payouts can target a single source without editing the underlying lists. (e.g. the option 'config' uses the stashes defined by 'stashes'; the option 'remote' uses the stashes fetched from 'stashes-url'; the option 'pools' uses the stashes nominated by the pools defined by 'pool-ids') [default: config,remote,pools]",
+        ))
     .arg(
       Arg::with_name("github-pat")
         .long("github-pat")
@@ -559,20 +1840,27 @@ fn get_config() -> Config {
         .help(
           "Enables payouts and messages to be grouped and processed by main identity.",
         ))
+    .arg(
+      Arg::with_name("group-identity-threshold")
+        .long("group-identity-threshold")
+        .takes_value(true)
+        .help(
+          "Payouts and messages are grouped and processed by main identity only when the number of distinct identities is greater than the threshold. Below it a single message is sent. With '--enable-group-identity' set, grouping is always enabled. [default: 0 (disabled)]",
+        ))
     .arg(
       Arg::with_name("substrate-ws-url")
         .short("w")
         .long("substrate-ws-url")
         .takes_value(true)
         .help(
-          "Substrate websocket endpoint for which 'crunch' will try to connect. (e.g. wss://rpc.turboflakes.io:443/kusama) (NOTE: substrate_ws_url takes precedence than <CHAIN> argument)",
+          "Substrate websocket endpoint for which 'crunch' will try to connect. Multiple endpoints could be defined comma-separated and are tried in order when connecting, the first reachable endpoint is used until 'crunch' reconnects after an error. (e.g. wss://rpc.turboflakes.io:443/kusama,wss://kusama.dotters.network) (NOTE: substrate_ws_url takes precedence than <CHAIN> argument)",
         ))
     .arg(
       Arg::with_name("substrate-people-ws-url")
         .long("substrate-people-ws-url")
         .takes_value(true)
         .help(
-          "Substrate websocket endpoint for which 'crunch' will try to connect and retrieve identities from. (e.g. wss://sys.turboflakes.io:443/people-kusama)",
+          "Substrate websocket endpoint for which 'crunch' will try to connect and retrieve identities from. Multiple endpoints could be defined comma-separated and are tried in order when connecting, the first reachable endpoint is used until 'crunch' reconnects after an error. (e.g. wss://sys.turboflakes.io:443/people-kusama,wss://people-kusama.dotters.network)",
         ),
     )
     .arg(
@@ -585,7 +1873,16 @@ fn get_config() -> Config {
         .help(
           "Sets a custom config file path. The config file contains 'crunch' configuration variables.",
         ))
-    .get_matches();
+}
+
+/// The 'rewards' subcommand is an alias of 'flakes' with a boring (no emoji) report style
+fn is_boring(matches: &ArgMatches) -> bool {
+    matches.subcommand_name() == Some("rewards")
+}
+
+/// Inject dotenv and env vars into the Config struct
+fn get_config() -> Config {
+    let matches = app().get_matches();
 
     // Try to load configuration from file first
     let config_path = matches.value_of("config-path").unwrap_or(".env");
@@ -670,6 +1967,10 @@ fn get_config() -> Config {
         env::set_var("CRUNCH_STASHES_URL", stashes_url);
     }
 
+    if let Some(stash_sources) = matches.value_of("stash-sources") {
+        env::set_var("CRUNCH_STASH_SOURCES", stash_sources);
+    }
+
     if let Some(github_pat) = matches.value_of("github-pat") {
         env::set_var("CRUNCH_GITHUB_PAT", github_pat);
     }
@@ -690,6 +1991,10 @@ fn get_config() -> Config {
         env::set_var("CRUNCH_GROUP_IDENTITY_ENABLED", "true");
     }
 
+    if let Some(group_identity_threshold) = matches.value_of("group-identity-threshold") {
+        env::set_var("CRUNCH_GROUP_IDENTITY_THRESHOLD", group_identity_threshold);
+    }
+
     match matches.subcommand() {
         ("flakes", Some(flakes_matches)) | ("rewards", Some(flakes_matches)) => {
             let mode = flakes_matches.value_of("MODE").unwrap_or_default();
@@ -708,24 +2013,192 @@ fn get_config() -> Config {
                 env::set_var("CRUNCH_SEED_PATH", seed_path);
             }
 
+            if let Some(external_signer_cmd) =
+                flakes_matches.value_of("external-signer-cmd")
+            {
+                env::set_var("CRUNCH_EXTERNAL_SIGNER_CMD", external_signer_cmd);
+            }
+
+            if let Some(external_signer_account) =
+                flakes_matches.value_of("external-signer-account")
+            {
+                env::set_var("CRUNCH_EXTERNAL_SIGNER_ACCOUNT", external_signer_account);
+            }
+
+            if let Some(external_signer_timeout) =
+                flakes_matches.value_of("external-signer-timeout")
+            {
+                env::set_var("CRUNCH_EXTERNAL_SIGNER_TIMEOUT", external_signer_timeout);
+            }
+
+            if let Some(funding_seed_path) = flakes_matches.value_of("funding-seed-path")
+            {
+                env::set_var("CRUNCH_FUNDING_SEED_PATH", funding_seed_path);
+            }
+
+            if let Some(threshold) = flakes_matches.value_of("signer-topup-threshold") {
+                env::set_var("CRUNCH_SIGNER_TOPUP_THRESHOLD", threshold);
+            }
+
+            if let Some(amount) = flakes_matches.value_of("signer-topup-amount") {
+                env::set_var("CRUNCH_SIGNER_TOPUP_AMOUNT", amount);
+            }
+
             if let Some(maximum_payouts) = flakes_matches.value_of("maximum-payouts") {
                 env::set_var("CRUNCH_MAXIMUM_PAYOUTS", maximum_payouts);
             }
 
+            if let Some(maximum_payouts_overrides) =
+                flakes_matches.value_of("maximum-payouts-overrides")
+            {
+                env::set_var(
+                    "CRUNCH_MAXIMUM_PAYOUTS_OVERRIDES",
+                    maximum_payouts_overrides,
+                );
+            }
+
             if let Some(maximum_history_eras) =
                 flakes_matches.value_of("maximum-history-eras")
             {
                 env::set_var("CRUNCH_MAXIMUM_HISTORY_ERAS", maximum_history_eras);
             }
 
+            if let Some(skip_recent_eras) = flakes_matches.value_of("skip-recent-eras") {
+                env::set_var("CRUNCH_SKIP_RECENT_ERAS", skip_recent_eras);
+            }
+
+            if flakes_matches.is_present("respect-max-eras-in-verbose") {
+                env::set_var("CRUNCH_RESPECT_MAX_ERAS_IN_VERBOSE", "true");
+            }
+
+            if let Some(era_chunk_size) = flakes_matches.value_of("era-chunk-size") {
+                env::set_var("CRUNCH_ERA_CHUNK_SIZE", era_chunk_size);
+            }
+
+            if flakes_matches.is_present("latest-era-only") {
+                env::set_var("CRUNCH_LATEST_ERA_ONLY", "true");
+            }
+
+            if let Some(era_scan_concurrency) =
+                flakes_matches.value_of("era-scan-concurrency")
+            {
+                env::set_var("CRUNCH_ERA_SCAN_CONCURRENCY", era_scan_concurrency);
+            }
+
+            if flakes_matches.is_present("enable-skip-inactive-eras") {
+                env::set_var("CRUNCH_SKIP_INACTIVE_ERAS_ENABLED", "true");
+            }
+
+            if flakes_matches.is_present("enable-skip-legacy-claimed") {
+                env::set_var("CRUNCH_SKIP_LEGACY_CLAIMED_ENABLED", "true");
+            }
+
+            if flakes_matches.is_present("enable-skip-zero-reward-eras") {
+                env::set_var("CRUNCH_SKIP_ZERO_REWARD_ERAS_ENABLED", "true");
+            }
+
+            if flakes_matches.is_present("enable-confirm-rewards") {
+                env::set_var("CRUNCH_CONFIRM_REWARDS_ENABLED", "true");
+            }
+
+            if flakes_matches.is_present("enable-continue-on-batch-failure") {
+                env::set_var("CRUNCH_CONTINUE_ON_BATCH_FAILURE_ENABLED", "true");
+            }
+
+            if flakes_matches.is_present("dry-run") {
+                env::set_var("CRUNCH_DRY_RUN_ENABLED", "true");
+            }
+
+            if flakes_matches.is_present("enable-log-all-events") {
+                env::set_var("CRUNCH_LOG_ALL_EVENTS_ENABLED", "true");
+            }
+
+            if flakes_matches.is_present("enable-risk-check") {
+                env::set_var("CRUNCH_RISK_CHECK_ENABLED", "true");
+            }
+
+            if flakes_matches.is_present("enable-export") {
+                env::set_var("CRUNCH_EXPORT_ENABLED", "true");
+            }
+
+            if let Some(export_path) = flakes_matches.value_of("export-path") {
+                env::set_var("CRUNCH_EXPORT_PATH", export_path);
+            }
+
+            if flakes_matches.is_present("export-raw-points") {
+                env::set_var("CRUNCH_EXPORT_RAW_POINTS", "true");
+            }
+
+            if flakes_matches.is_present("export-nominator-detail") {
+                env::set_var("CRUNCH_EXPORT_NOMINATOR_DETAIL", "true");
+            }
+
+            if flakes_matches.is_present("enable-metrics") {
+                env::set_var("CRUNCH_METRICS_ENABLED", "true");
+            }
+
+            if let Some(metrics_port) = flakes_matches.value_of("metrics-port") {
+                env::set_var("CRUNCH_METRICS_PORT", metrics_port);
+            }
+
+            if let Some(dashboard_port) = flakes_matches.value_of("dashboard-port") {
+                env::set_var("CRUNCH_DASHBOARD_PORT", dashboard_port);
+            }
+
+            if let Some(max_stashes_per_run) =
+                flakes_matches.value_of("max-stashes-per-run")
+            {
+                env::set_var("CRUNCH_MAX_STASHES_PER_RUN", max_stashes_per_run);
+            }
+
+            if let Some(state_path) = flakes_matches.value_of("state-path") {
+                env::set_var("CRUNCH_STATE_PATH", state_path);
+            }
+
+            if let Some(pause_file) = flakes_matches.value_of("pause-file") {
+                env::set_var("CRUNCH_PAUSE_FILE", pause_file);
+            }
+
+            if let Some(era_log_size) = flakes_matches.value_of("era-log-size") {
+                env::set_var("CRUNCH_ERA_LOG_SIZE", era_log_size);
+            }
+
             if let Some(maximum_calls) = flakes_matches.value_of("maximum-calls") {
                 env::set_var("CRUNCH_MAXIMUM_CALLS", maximum_calls);
             }
 
+            if let Some(max_total_calls) = flakes_matches.value_of("max-total-calls") {
+                env::set_var("CRUNCH_MAX_TOTAL_CALLS", max_total_calls);
+            }
+
+            if let Some(only_pages) = flakes_matches.value_of("only-pages") {
+                env::set_var("CRUNCH_ONLY_PAGES", only_pages);
+            }
+
+            if let Some(batch_size_strategy) =
+                flakes_matches.value_of("batch-size-strategy")
+            {
+                env::set_var("CRUNCH_BATCH_SIZE_STRATEGY", batch_size_strategy);
+            }
+
+            if let Some(max_batch_fee) = flakes_matches.value_of("max-batch-fee") {
+                env::set_var("CRUNCH_MAX_BATCH_FEE", max_batch_fee);
+            }
+
+            if let Some(max_weight_fraction) =
+                flakes_matches.value_of("max-weight-fraction")
+            {
+                env::set_var("CRUNCH_MAX_WEIGHT_FRACTION", max_weight_fraction);
+            }
+
             if flakes_matches.is_present("debug") {
                 env::set_var("CRUNCH_IS_DEBUG", "true");
             }
 
+            if flakes_matches.is_present("no-color") {
+                env::set_var("CRUNCH_NO_COLOR", "true");
+            }
+
             if flakes_matches.is_present("short") {
                 env::set_var("CRUNCH_IS_SHORT", "true");
             }
@@ -746,6 +2219,10 @@ fn get_config() -> Config {
                 env::set_var("CRUNCH_MATRIX_PUBLIC_ROOM_DISABLED", "true");
             }
 
+            if flakes_matches.is_present("enable-public-matrix-room-redacted") {
+                env::set_var("CRUNCH_MATRIX_PUBLIC_ROOM_REDACTED_ENABLED", "true");
+            }
+
             if let Some(matrix_user) = flakes_matches.value_of("matrix-user") {
                 env::set_var("CRUNCH_MATRIX_ACCOUNT", matrix_user);
             }
@@ -760,14 +2237,40 @@ fn get_config() -> Config {
                 env::set_var("CRUNCH_MATRIX_BOT_PASSWORD", matrix_bot_password);
             }
 
+            if let Some(matrix_alert_room) = flakes_matches.value_of("matrix-alert-room")
+            {
+                env::set_var("CRUNCH_MATRIX_ALERT_ROOM", matrix_alert_room);
+            }
+
             if let Some(error_interval) = flakes_matches.value_of("error-interval") {
                 env::set_var("CRUNCH_ERROR_INTERVAL", error_interval);
             }
 
+            if let Some(min_run_interval) = flakes_matches.value_of("min-run-interval") {
+                env::set_var("CRUNCH_MIN_RUN_INTERVAL_SECONDS", min_run_interval);
+            }
+
+            if let Some(keepalive_seconds) = flakes_matches.value_of("keepalive-seconds")
+            {
+                env::set_var("CRUNCH_KEEPALIVE_SECONDS", keepalive_seconds);
+            }
+
+            if let Some(min_random_wait) = flakes_matches.value_of("min-random-wait") {
+                env::set_var("CRUNCH_MIN_RANDOM_WAIT", min_random_wait);
+            }
+
+            if let Some(max_random_wait) = flakes_matches.value_of("max-random-wait") {
+                env::set_var("CRUNCH_MAX_RANDOM_WAIT", max_random_wait);
+            }
+
             if let Some(pool_ids) = flakes_matches.value_of("pool-ids") {
                 env::set_var("CRUNCH_POOL_IDS", pool_ids);
             }
 
+            if flakes_matches.is_present("strict-pools") {
+                env::set_var("CRUNCH_STRICT_POOLS", "true");
+            }
+
             if let Some(tx_tip) = flakes_matches.value_of("tx-tip") {
                 env::set_var("CRUNCH_TX_TIP", tx_tip);
             }
@@ -782,10 +2285,24 @@ fn get_config() -> Config {
                 env::set_var("CRUNCH_POOL_COMPOUND_THRESHOLD", threshold);
             }
 
+            if let Some(interval) =
+                flakes_matches.value_of("pool-compound-min-interval-eras")
+            {
+                env::set_var("CRUNCH_POOL_COMPOUND_MIN_INTERVAL_ERAS", interval);
+            }
+
             if flakes_matches.is_present("enable-pool-only-operator-compound") {
                 env::set_var("CRUNCH_POOL_ONLY_OPERATOR_COMPOUND_ENABLED", "true");
             }
 
+            if flakes_matches.is_present("operator-compound-without-permission") {
+                env::set_var("CRUNCH_OPERATOR_COMPOUND_REQUIRES_PERMISSION", "false");
+            }
+
+            if flakes_matches.is_present("enable-pool-operator-membership-check") {
+                env::set_var("CRUNCH_POOL_OPERATOR_MEMBERSHIP_CHECK_ENABLED", "true");
+            }
+
             if flakes_matches.is_present("enable-pool-members-compound") {
                 env::set_var("CRUNCH_POOL_MEMBERS_COMPOUND_ENABLED", "true");
             }
@@ -794,6 +2311,12 @@ fn get_config() -> Config {
                 env::set_var("CRUNCH_POOL_ACTIVE_NOMINEES_PAYOUT_ENABLED", "true");
             }
 
+            if let Some(pool_nominee_era_offset) =
+                flakes_matches.value_of("pool-nominee-era-offset")
+            {
+                env::set_var("CRUNCH_POOL_NOMINEE_ERA_OFFSET", pool_nominee_era_offset);
+            }
+
             if flakes_matches.is_present("enable-pool-all-nominees-payout") {
                 env::set_var("CRUNCH_POOL_ALL_NOMINEES_PAYOUT_ENABLED", "true");
             }
@@ -801,25 +2324,191 @@ fn get_config() -> Config {
             if flakes_matches.is_present("enable-onet-api") {
                 env::set_var("CRUNCH_ONET_API_ENABLED", "true");
             }
+
+            if let Some(report_template) = flakes_matches.value_of("report-template") {
+                env::set_var("CRUNCH_REPORT_TEMPLATE_PATH", report_template);
+            }
+
+            if let Some(report_currency) = flakes_matches.value_of("report-currency") {
+                env::set_var("CRUNCH_FIAT_CURRENCY", report_currency);
+            }
+
+            if let Some(report_sort) = flakes_matches.value_of("report-sort") {
+                env::set_var("CRUNCH_REPORT_SORT", report_sort);
+            }
+
+            if let Some(report_locale) = flakes_matches.value_of("report-locale") {
+                env::set_var("CRUNCH_LOCALE", report_locale);
+            }
+
+            if let Some(report_timezone) = flakes_matches.value_of("report-timezone") {
+                env::set_var("CRUNCH_TIMEZONE", report_timezone);
+            }
+
+            if let Some(identity_max_len) = flakes_matches.value_of("identity-max-len") {
+                env::set_var("CRUNCH_IDENTITY_MAX_LEN", identity_max_len);
+            }
+
+            if flakes_matches.is_present("strip-emoji") {
+                env::set_var("CRUNCH_STRIP_EMOJI", "true");
+            }
+
+            if flakes_matches.is_present("report-minimal-on-success") {
+                env::set_var("CRUNCH_REPORT_MINIMAL_ON_SUCCESS", "true");
+            }
+
+            if flakes_matches.is_present("report-include-controller") {
+                env::set_var("CRUNCH_REPORT_INCLUDE_CONTROLLER", "true");
+            }
+
+            if flakes_matches.is_present("report-compare-previous-run") {
+                env::set_var("CRUNCH_REPORT_COMPARE_PREVIOUS_RUN", "true");
+            }
+
+            if let Some(template) =
+                flakes_matches.value_of("report-validator-url-template")
+            {
+                env::set_var("CRUNCH_REPORT_VALIDATOR_URL_TEMPLATE", template);
+            }
+
+            if let Some(template) =
+                flakes_matches.value_of("report-extrinsic-url-template")
+            {
+                env::set_var("CRUNCH_REPORT_EXTRINSIC_URL_TEMPLATE", template);
+            }
+
+            if let Some(high_commission_warn) =
+                flakes_matches.value_of("high-commission-warn")
+            {
+                env::set_var("CRUNCH_HIGH_COMMISSION_WARN", high_commission_warn);
+            }
+
+            if flakes_matches.is_present("report-split-pools") {
+                env::set_var("CRUNCH_REPORT_SPLIT_POOLS", "true");
+            }
         }
-        ("view", Some(_)) => {
+        ("view", Some(view_matches)) => {
             env::set_var("CRUNCH_ONLY_VIEW", "true");
+
+            if let Some(at_block) = view_matches.value_of("at-block") {
+                env::set_var("CRUNCH_AT_BLOCK", at_block);
+            }
+
+            if view_matches.is_present("json") {
+                env::set_var("CRUNCH_VIEW_JSON_ENABLED", "true");
+            }
+        }
+        ("pending", Some(_)) => {
+            env::set_var("CRUNCH_ONLY_PENDING", "true");
+        }
+        ("notify-test", Some(_)) => {
+            env::set_var("CRUNCH_ONLY_NOTIFY_TEST", "true");
+        }
+        ("dump-metadata", Some(dump_metadata_matches)) => {
+            env::set_var("CRUNCH_ONLY_DUMP_METADATA", "true");
+
+            if let Some(output) = dump_metadata_matches.value_of("output") {
+                env::set_var("CRUNCH_DUMP_METADATA_PATH", output);
+            }
         }
         _ => {
             warn!("Besides subcommand 'flakes' being the default subcommand, would be cool to have it visible, so that CLI becomes more expressive (e.g. 'crunch flakes daily')");
         }
     }
 
-    if matches.is_present("rewards") {
+    if is_boring(&matches) {
         env::set_var("CRUNCH_IS_BORING", "true");
     }
 
+    // NOTE: CRUNCH_DEBUG_LOG_ALL_EVENTS is read as an alias of CRUNCH_LOG_ALL_EVENTS_ENABLED
+    if env::var("CRUNCH_LOG_ALL_EVENTS_ENABLED").is_err() {
+        if let Ok(log_all_events) = env::var("CRUNCH_DEBUG_LOG_ALL_EVENTS") {
+            env::set_var("CRUNCH_LOG_ALL_EVENTS_ENABLED", log_all_events);
+        }
+    }
+
     match envy::prefixed("CRUNCH_").from_env::<Config>() {
-        Ok(config) => config,
-        Err(error) => panic!("Configuration error: {:#?}", error),
+        Ok(config) => {
+            // Validate ONE-T url with the {network} placeholder replaced by any network name
+            if !config.onet_api_url.is_empty() {
+                if let Err(e) =
+                    url::Url::parse(&config.onet_api_url.replace("{network}", "polkadot"))
+                {
+                    panic!(
+                        "Configuration error: Invalid ONE-T api url {} error: {}",
+                        config.onet_api_url, e
+                    );
+                }
+            }
+            if let Err(e) = parse_era_pages(&config.only_pages) {
+                panic!("Configuration error: Invalid only pages {}", e);
+            }
+            if let Err(e) =
+                parse_maximum_payouts_overrides(&config.maximum_payouts_overrides)
+            {
+                panic!(
+                    "Configuration error: Invalid maximum payouts overrides {}",
+                    e
+                );
+            }
+            if !(0.1..=1.0).contains(&config.max_weight_fraction) {
+                panic!(
+                    "Configuration error: Invalid max weight fraction {}, expected a value between 0.1 and 1.0",
+                    config.max_weight_fraction
+                );
+            }
+            if config.timezone.parse::<Tz>().is_err() {
+                panic!(
+                    "Configuration error: Invalid timezone '{}', expected an IANA time zone name (e.g. Europe/Lisbon)",
+                    config.timezone
+                );
+            }
+            config
+        }
+        // NOTE: envy errors might include the value that failed to be parsed
+        Err(error) => panic!(
+            "Configuration error: {}",
+            redact_secrets(format!("{:#?}", error), &secret_env_values())
+        ),
     }
 }
 
+/// Parses a comma separated list of era and page pairs (e.g. 1500:0,1501:1)
+pub fn parse_era_pages(value: &str) -> Result<Vec<(u32, u32)>, String> {
+    value
+        .split(',')
+        .map(|pair| pair.trim())
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| match pair.split_once(':') {
+            Some((era, page)) => match (era.trim().parse(), page.trim().parse()) {
+                (Ok(era), Ok(page)) => Ok((era, page)),
+                _ => Err(format!("'{}' expected numeric era and page", pair)),
+            },
+            None => Err(format!("'{}' expected in the form era:page", pair)),
+        })
+        .collect()
+}
+
+/// Parses a comma separated list of stash and maximum payouts pairs (e.g. stash_1:0,stash_2:8)
+pub fn parse_maximum_payouts_overrides(
+    value: &str,
+) -> Result<Vec<(String, u32)>, String> {
+    value
+        .split(',')
+        .map(|pair| pair.trim())
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| match pair.split_once(':') {
+            Some((stash, count)) if !stash.trim().is_empty() => {
+                match count.trim().parse() {
+                    Ok(count) => Ok((stash.trim().to_string(), count)),
+                    _ => Err(format!("'{}' expected numeric maximum payouts", pair)),
+                }
+            }
+            _ => Err(format!("'{}' expected in the form stash:count", pair)),
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -835,4 +2524,59 @@ mod tests {
         let config = &CONFIG;
         assert_ne!(config.substrate_ws_url, "".to_string());
     }
+
+    #[test]
+    fn it_redacts_secrets_from_config_debug() {
+        let mut config = get_config();
+        config.matrix_bot_password = "super-secret-password".to_string();
+        config.github_pat = "github_pat_123ABC".to_string();
+        let debug = format!("{:?}", config);
+        assert!(!debug.contains("super-secret-password"));
+        assert!(!debug.contains("github_pat_123ABC"));
+        assert!(debug.contains("matrix_bot_password: \"***\""));
+    }
+
+    #[test]
+    fn it_redacts_secrets_from_error_messages() {
+        let message = redact_secrets(
+            "invalid value 'super-secret-password' provided by CRUNCH_MATRIX_BOT_PASSWORD"
+                .to_string(),
+            &["".to_string(), "super-secret-password".to_string()],
+        );
+        assert_eq!(
+            message,
+            "invalid value '***' provided by CRUNCH_MATRIX_BOT_PASSWORD"
+        );
+    }
+
+    #[test]
+    fn it_sets_boring_style_for_the_rewards_subcommand() {
+        let matches = app().get_matches_from(vec!["crunch", "rewards", "once"]);
+        assert!(is_boring(&matches));
+        let matches = app().get_matches_from(vec!["crunch", "flakes", "once"]);
+        assert!(!is_boring(&matches));
+    }
+
+    #[test]
+    fn it_parses_era_pages() {
+        assert_eq!(
+            parse_era_pages("1500:0, 1501:1,"),
+            Ok(vec![(1500, 0), (1501, 1)])
+        );
+        assert_eq!(parse_era_pages(""), Ok(vec![]));
+        assert!(parse_era_pages("1500").is_err());
+        assert!(parse_era_pages("1500:a").is_err());
+    }
+
+    #[test]
+    fn it_parses_maximum_payouts_overrides() {
+        assert_eq!(
+            parse_maximum_payouts_overrides("stash_1:0, stash_2:8,"),
+            Ok(vec![("stash_1".to_string(), 0), ("stash_2".to_string(), 8)])
+        );
+        assert_eq!(parse_maximum_payouts_overrides(""), Ok(vec![]));
+        assert!(parse_maximum_payouts_overrides("stash_1").is_err());
+        assert!(parse_maximum_payouts_overrides(":4").is_err());
+        assert!(parse_maximum_payouts_overrides("stash_1:a").is_err());
+    }
 }
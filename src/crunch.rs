@@ -18,36 +18,73 @@
 // LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
-use crate::config::CONFIG;
+use crate::config::{parse_maximum_payouts_overrides, CONFIG};
 use crate::errors::CrunchError;
 use crate::matrix::Matrix;
+use crate::metrics;
+use crate::report::{
+    EraIndex, PayoutSummary, Report, StashSummary, StashesWindow, Validators,
+};
 use crate::runtimes::{
     kusama, paseo, polkadot,
     support::{ChainPrefix, ChainTokenSymbol, SupportedRuntime},
     westend,
 };
-use async_std::task;
+use crate::state::{self, EraRecord, RunRecord, State};
+use async_std::{future, task};
+use base64::{engine::general_purpose, Engine};
+use codec::Decode;
+use crypto_secretbox::{
+    aead::{Aead, KeyInit},
+    Key as SecretBoxKey, Nonce, XSalsa20Poly1305,
+};
+use futures::{Stream, StreamExt};
 use log::{debug, error, info, warn};
 use rand::Rng;
 use regex::Regex;
 use serde::Deserialize;
-use std::{convert::TryInto, fs, result::Result, str::FromStr, thread, time};
+use serde_json::Value;
+use std::{
+    cmp,
+    collections::HashMap,
+    convert::TryInto,
+    fs,
+    io::Write,
+    ops::{Range, RangeInclusive},
+    process::{Command, Stdio},
+    result::Result,
+    str::FromStr,
+    thread, time,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 use subxt::{
     backend::{
-        legacy::{rpc_methods::StorageKey, LegacyRpcMethods},
+        legacy::{
+            rpc_methods::{StorageKey, SystemProperties},
+            LegacyRpcMethods,
+        },
         rpc::{
             reconnecting_rpc_client::{Client as ReconnectingClient, ExponentialBackoff},
             RpcClient,
         },
     },
-    ext::sp_core::crypto,
+    config::{Config, ExtrinsicParams},
+    events::EventDetails,
+    ext::{scale_value::Composite, sp_core::crypto},
     lightclient::{LightClient, LightClientError, LightClientRpc},
-    utils::{validate_url_is_secure, AccountId32},
+    storage::{Address, Storage},
+    tx::{Payload, Signer, TxProgress},
+    utils::{
+        validate_url_is_secure, AccountId32, MultiAddress, MultiSignature, Yes, H256,
+    },
     OnlineClient, SubstrateConfig,
 };
 
-use subxt_signer::{sr25519::Keypair, SecretUri};
+use subxt_signer::{
+    sr25519::{self, Keypair},
+    SecretUri,
+};
 
 pub type ValidatorIndex = Option<usize>;
 pub type ValidatorAmount = u128;
@@ -55,6 +92,18 @@ pub type NominatorsAmount = u128;
 
 type Message = Vec<String>;
 
+/// Maximum number of retries of a people chain query while the RPC connection is reestablished
+const PEOPLE_CLIENT_MAX_RETRIES: u32 = 5;
+
+/// Maximum number of retries of a storage read during the unclaimed eras scan
+const SCAN_MAX_RETRIES: u32 = 3;
+
+/// Range of plausible values for the staking history depth constant
+pub const HISTORY_DEPTH_PLAUSIBLE_RANGE: RangeInclusive<u32> = 1..=1000;
+
+/// Minimum number of blocks a mortal transaction is valid for
+const MIN_TX_MORTAL_PERIOD: u64 = 4;
+
 trait MessageTrait {
     fn log(&self);
     fn show_or_hide(&mut self, value: String, hidden: bool);
@@ -89,19 +138,39 @@ impl MessageTrait for Message {
 //     RpcClient::from_insecure_url(url).await
 // }
 
-pub async fn create_substrate_rpc_client_from_url(
-    url: &str,
-) -> Result<ReconnectingClient, subxt::error::RpcError> {
-    if let Err(_) = validate_url_is_secure(url) {
-        warn!("Insecure URL provided: {}", url);
-    };
-    info!("Using RPC endpoint {}", url);
-    ReconnectingClient::builder()
-        .retry_policy(
-            ExponentialBackoff::from_millis(100).max_delay(time::Duration::from_secs(10)),
-        )
-        .build(url.to_string())
-        .await
+/// Try each of the comma-separated RPC endpoints, in the order they are defined,
+/// and return a client for the first one that responds.
+/// NOTE: The client keeps reconnecting to the endpoint selected, the endpoints are only
+/// tried again when the client is recreated (e.g. when crunch restarts after an error)
+pub async fn create_substrate_rpc_client_from_urls(
+    urls: &str,
+) -> Result<ReconnectingClient, CrunchError> {
+    let mut last_error: Option<CrunchError> = None;
+    for url in urls
+        .split(',')
+        .map(|url| url.trim())
+        .filter(|url| !url.is_empty())
+    {
+        if let Err(_) = validate_url_is_secure(url) {
+            warn!("Insecure URL provided: {}", url);
+        };
+        info!("Using RPC endpoint {}", url);
+        match ReconnectingClient::builder()
+            .retry_policy(
+                ExponentialBackoff::from_millis(100)
+                    .max_delay(time::Duration::from_secs(10)),
+            )
+            .build(url.to_string())
+            .await
+        {
+            Ok(rpc) => return Ok(rpc),
+            Err(e) => {
+                warn!("RPC endpoint {} not available: {}", url, e);
+                last_error = Some(e.into());
+            }
+        }
+    }
+    Err(last_error.unwrap_or_else(|| "No RPC endpoint defined".into()))
 }
 
 pub async fn create_substrate_client_from_rpc_client(
@@ -137,7 +206,8 @@ pub async fn create_substrate_rpc_client_from_config() -> Result<RpcClient, Crun
             create_light_client_from_relay_chain_specs(&config.chain_name).await?;
         return Ok(rpc.into());
     } else {
-        let rpc = create_substrate_rpc_client_from_url(&config.substrate_ws_url).await?;
+        // NOTE: multiple endpoints could be defined as fallbacks
+        let rpc = create_substrate_rpc_client_from_urls(&config.substrate_ws_url).await?;
         return Ok(rpc.into());
     }
 }
@@ -170,15 +240,7 @@ pub async fn create_or_await_substrate_node_client() -> (
                 ));
 
                 let chain_token_symbol: ChainTokenSymbol =
-                    if let Some(token_symbol) = properties.get("tokenSymbol") {
-                        use serde_json::Value::String;
-                        match token_symbol {
-                            String(token_symbol) => token_symbol.to_string(),
-                            _ => unreachable!("Token symbol with wrong type"),
-                        }
-                    } else {
-                        String::from("")
-                    };
+                    get_token_symbol(&properties).unwrap_or_default();
 
                 info!(
                     "Connected to {} network * client {} v{}",
@@ -218,8 +280,9 @@ pub async fn create_people_rpc_client_from_config() -> Result<RpcClient, CrunchE
         let rpc = create_light_client_from_people_chain_specs(&config.chain_name).await?;
         return Ok(rpc.into());
     } else {
-        let rpc =
-            create_substrate_rpc_client_from_url(&config.substrate_people_ws_url).await?;
+        // NOTE: multiple people endpoints could be defined as fallbacks
+        let rpc = create_substrate_rpc_client_from_urls(&config.substrate_people_ws_url)
+            .await?;
         return Ok(rpc.into());
     }
 }
@@ -266,12 +329,22 @@ pub async fn create_or_await_people_client() -> OnlineClient<SubstrateConfig> {
 //         .expect("constructed from known-good static value; qed")
 // }
 
-/// Helper function to generate a keypair from the content of the seed file
-pub fn get_keypair_from_seed_file() -> Result<Keypair, CrunchError> {
-    let config = CONFIG.clone();
-
+/// Helper function to generate a keypair from the content of the seed file, JSON
+/// keystore files are decrypted with the password given
+pub fn get_keypair_from_seed_file(
+    seed_path: &str,
+    password: &str,
+) -> Result<SeedKeypair, CrunchError> {
     // load data from seed file
-    let data = fs::read_to_string(config.seed_path)?;
+    let data = fs::read_to_string(seed_path)?;
+
+    // decrypt JSON keystore files exported from polkadot.js
+    if seed_path.ends_with(".json") || data.trim_start().starts_with('{') {
+        let keypair = decrypt_keystore(&data, password).map_err(|e| {
+            CrunchError::Other(format!("Keystore file could not be decrypted: {e}"))
+        })?;
+        return Ok(SeedKeypair::Keystore(keypair));
+    }
 
     // clear control characters from data
     let re = Regex::new(r"[\x00-\x1F]").unwrap();
@@ -279,7 +352,289 @@ pub fn get_keypair_from_seed_file() -> Result<Keypair, CrunchError> {
 
     // parse data into a secret
     let uri = SecretUri::from_str(&data)?;
-    Ok(Keypair::from_uri(&uri)?)
+    Ok(SeedKeypair::Uri(Keypair::from_uri(&uri)?))
+}
+
+#[derive(Deserialize)]
+struct Keystore {
+    encoded: String,
+    encoding: KeystoreEncoding,
+}
+
+#[derive(Deserialize)]
+struct KeystoreEncoding {
+    content: Vec<String>,
+    #[serde(rename = "type")]
+    kind: Vec<String>,
+}
+
+const SCRYPT_LENGTH: usize = 32 + 3 * 4;
+const NONCE_LENGTH: usize = 24;
+const PKCS8_HEADER: [u8; 16] = [48, 83, 2, 1, 1, 48, 5, 6, 3, 43, 101, 112, 4, 34, 4, 32];
+const PKCS8_DIVIDER: [u8; 5] = [161, 35, 3, 33, 0];
+
+/// Decrypt the sr25519 keypair of a JSON keystore exported from polkadot.js, encoded as
+/// the scrypt salt and params, the nonce and the xsalsa20-poly1305 encrypted PKCS8 keypair
+fn decrypt_keystore(data: &str, password: &str) -> Result<schnorrkel::Keypair, String> {
+    let keystore: Keystore = serde_json::from_str(data).map_err(|e| e.to_string())?;
+    if !keystore.encoding.content.iter().any(|c| c == "sr25519") {
+        return Err(format!(
+            "unsupported key type {:?}",
+            keystore.encoding.content
+        ));
+    }
+    if !keystore.encoding.kind.iter().any(|t| t == "scrypt")
+        || !keystore
+            .encoding
+            .kind
+            .iter()
+            .any(|t| t == "xsalsa20-poly1305")
+    {
+        return Err(format!(
+            "unsupported encryption {:?}",
+            keystore.encoding.kind
+        ));
+    }
+
+    let encoded = general_purpose::STANDARD
+        .decode(keystore.encoded.as_bytes())
+        .map_err(|e| e.to_string())?;
+    if encoded.len() < SCRYPT_LENGTH + NONCE_LENGTH {
+        return Err("encoded data too short".into());
+    }
+
+    // derive the secretbox key from the password with the scrypt params of the file
+    let (salt, params) = encoded[..SCRYPT_LENGTH].split_at(32);
+    let param =
+        |i: usize| u32::from_le_bytes(params[i * 4..i * 4 + 4].try_into().unwrap());
+    let (n, p, r) = (param(0), param(1), param(2));
+    if !n.is_power_of_two() {
+        return Err(format!("invalid scrypt N {n}"));
+    }
+    let params = scrypt::Params::new(n.trailing_zeros() as u8, r, p, 64)
+        .map_err(|e| e.to_string())?;
+    let mut derived = [0u8; 64];
+    scrypt::scrypt(password.as_bytes(), salt, &params, &mut derived)
+        .map_err(|e| e.to_string())?;
+
+    let (nonce, encrypted) = encoded[SCRYPT_LENGTH..].split_at(NONCE_LENGTH);
+    let key: [u8; 32] = derived[..32].try_into().unwrap();
+    let nonce: [u8; NONCE_LENGTH] = nonce.try_into().unwrap();
+    let decrypted = XSalsa20Poly1305::new(&SecretBoxKey::from(key))
+        .decrypt(&Nonce::from(nonce), encrypted)
+        .map_err(|_| "invalid password".to_string())?;
+
+    // PKCS8 encoded as header, secret key, divider and public key
+    if decrypted.len() != PKCS8_HEADER.len() + 64 + PKCS8_DIVIDER.len() + 32
+        || decrypted[..16] != PKCS8_HEADER
+        || decrypted[80..85] != PKCS8_DIVIDER
+    {
+        return Err("invalid PKCS8 keypair".into());
+    }
+    let keypair = schnorrkel::SecretKey::from_ed25519_bytes(&decrypted[16..80])
+        .map_err(|e| e.to_string())?
+        .to_keypair();
+    if keypair.public.to_bytes()[..] != decrypted[85..] {
+        return Err("public key does not match the secret key".into());
+    }
+    Ok(keypair)
+}
+
+/// Keypair defined by the seed file, either from a secret uri or decrypted from a
+/// JSON keystore
+#[derive(Clone)]
+pub enum SeedKeypair {
+    Uri(Keypair),
+    Keystore(schnorrkel::Keypair),
+}
+
+impl Signer<SubstrateConfig> for SeedKeypair {
+    fn account_id(&self) -> AccountId32 {
+        match self {
+            Self::Uri(keypair) => keypair.public_key().into(),
+            Self::Keystore(keypair) => AccountId32(keypair.public.to_bytes()),
+        }
+    }
+
+    fn address(&self) -> MultiAddress<AccountId32, ()> {
+        self.account_id().into()
+    }
+
+    fn sign(&self, signer_payload: &[u8]) -> MultiSignature {
+        match self {
+            Self::Uri(keypair) => {
+                <Keypair as Signer<SubstrateConfig>>::sign(keypair, signer_payload)
+            }
+            Self::Keystore(keypair) => {
+                let context = schnorrkel::signing_context(b"substrate");
+                MultiSignature::Sr25519(
+                    keypair.sign(context.bytes(signer_payload)).to_bytes(),
+                )
+            }
+        }
+    }
+}
+
+/// Helper function to get the signer defined by the user, either an external signer
+/// command or the keypair from the content of the seed file
+pub fn get_signer_from_config() -> Result<CrunchSigner, CrunchError> {
+    let config = CONFIG.clone();
+    if !config.external_signer_cmd.is_empty() {
+        let account_id =
+            AccountId32::from_str(&config.external_signer_account).map_err(|e| {
+                CrunchError::ExternalSignerError(format!(
+                    "invalid account {} error: {e:?}",
+                    config.external_signer_account
+                ))
+            })?;
+        return Ok(CrunchSigner::External(ExternalSigner {
+            account_id,
+            cmd: config.external_signer_cmd,
+            timeout: time::Duration::from_secs(config.external_signer_timeout),
+        }));
+    }
+    Ok(CrunchSigner::Keypair(get_keypair_from_seed_file(
+        &config.seed_path,
+        &config.keystore_password,
+    )?))
+}
+
+/// Verify at startup that the external signer command, if defined, signs for the
+/// account defined by CRUNCH_EXTERNAL_SIGNER_ACCOUNT
+pub fn verify_external_signer_from_config() -> Result<(), CrunchError> {
+    if CONFIG.external_signer_cmd.is_empty() {
+        return Ok(());
+    }
+    if let CrunchSigner::External(external) = get_signer_from_config()? {
+        external.verify()?;
+        info!(
+            "External signer verified for account {}",
+            external.account_id
+        );
+    }
+    Ok(())
+}
+
+/// Transaction parameters accepted by the `CrunchSigner`
+pub type CrunchTxParams =
+    <<SubstrateConfig as Config>::ExtrinsicParams as ExtrinsicParams<SubstrateConfig>>::Params;
+
+/// Signer used to sign the payout extrinsics
+#[derive(Clone)]
+pub enum CrunchSigner {
+    Keypair(SeedKeypair),
+    External(ExternalSigner),
+}
+
+impl CrunchSigner {
+    pub fn account_id(&self) -> AccountId32 {
+        match self {
+            Self::Keypair(keypair) => keypair.account_id(),
+            Self::External(external) => external.account_id.clone(),
+        }
+    }
+
+    pub fn address(&self) -> MultiAddress<AccountId32, ()> {
+        self.account_id().into()
+    }
+
+    /// Sign and submit the call, if the external signer fails the call is not submitted
+    pub async fn sign_and_submit_then_watch<Call: Payload>(
+        &self,
+        api: &OnlineClient<SubstrateConfig>,
+        call: &Call,
+        params: CrunchTxParams,
+    ) -> Result<TxProgress<SubstrateConfig, OnlineClient<SubstrateConfig>>, CrunchError>
+    {
+        match self {
+            Self::Keypair(keypair) => Ok(api
+                .tx()
+                .sign_and_submit_then_watch(call, keypair, params)
+                .await?),
+            Self::External(external) => {
+                let partial = api
+                    .tx()
+                    .create_partial_signed(call, &external.account_id, params)
+                    .await?;
+                let signature = external.try_sign(&partial.signer_payload())?;
+                let signature = MultiSignature::Sr25519(signature.0);
+                Ok(partial
+                    .sign_with_address_and_signature(&self.address(), &signature)
+                    .submit_and_watch()
+                    .await?)
+            }
+        }
+    }
+}
+
+/// External signer command (e.g. a hardware wallet or HSM wrapper) which receives the
+/// signer payload hex encoded on stdin and writes back the sr25519 signature hex encoded on stdout
+#[derive(Clone)]
+pub struct ExternalSigner {
+    account_id: AccountId32,
+    cmd: String,
+    timeout: time::Duration,
+}
+
+impl ExternalSigner {
+    /// Sign a probe payload and verify the signature against the signer account
+    fn verify(&self) -> Result<(), CrunchError> {
+        let payload = format!("<Bytes>crunch probe {}</Bytes>", self.account_id);
+        let signature = self.try_sign(payload.as_bytes())?;
+        let public_key = sr25519::PublicKey(self.account_id.0);
+        if !sr25519::verify(&signature, payload.as_bytes(), &public_key) {
+            return Err(CrunchError::ExternalSignerError(format!(
+                "probe signature does not match account {}",
+                self.account_id
+            )));
+        }
+        Ok(())
+    }
+
+    fn try_sign(&self, signer_payload: &[u8]) -> Result<sr25519::Signature, CrunchError> {
+        let mut child = Command::new("sh")
+            .arg("-c")
+            .arg(&self.cmd)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(format!("0x{}", hex::encode(signer_payload)).as_bytes())?;
+        }
+
+        // Wait for the command to exit until the timeout is reached
+        let deadline = time::Instant::now() + self.timeout;
+        while child.try_wait()?.is_none() {
+            if time::Instant::now() >= deadline {
+                child.kill()?;
+                child.wait()?;
+                return Err(CrunchError::ExternalSignerError(format!(
+                    "command timed out after {} seconds",
+                    self.timeout.as_secs()
+                )));
+            }
+            thread::sleep(time::Duration::from_millis(100));
+        }
+
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            return Err(CrunchError::ExternalSignerError(format!(
+                "command failed with {}",
+                output.status
+            )));
+        }
+
+        let signature = String::from_utf8_lossy(&output.stdout);
+        let bytes =
+            hex::decode(signature.trim().trim_start_matches("0x")).map_err(|e| {
+                CrunchError::ExternalSignerError(format!("invalid signature: {e}"))
+            })?;
+        let signature: [u8; 64] = bytes.try_into().map_err(|_| {
+            CrunchError::ExternalSignerError("signature must be 64 bytes".into())
+        })?;
+        Ok(sr25519::Signature(signature))
+    }
 }
 
 pub struct Crunch {
@@ -335,10 +690,279 @@ impl Crunch {
         &self.client
     }
 
+    pub fn runtime(&self) -> SupportedRuntime {
+        self.runtime
+    }
+
+    /// Returns the maximum number of calls in a single batch defined by the user
+    /// or the default for the network if not set
+    pub fn maximum_calls(&self) -> u32 {
+        let config = CONFIG.clone();
+        if config.maximum_calls > 0 {
+            return config.maximum_calls;
+        }
+        self.runtime.default_maximum_calls()
+    }
+
+    /// Returns the maximum number of pool compound calls in a single batch defined by
+    /// the user or the default for the network if not set
+    pub fn maximum_pool_members_calls(&self) -> u32 {
+        let config = CONFIG.clone();
+        if config.maximum_pool_members_calls > 0 {
+            return config.maximum_pool_members_calls;
+        }
+        self.runtime.default_maximum_pool_members_calls()
+    }
+
+    /// Verify that the genesis hash of the connected chain matches the network selected
+    /// by the user, or the network detected from the token symbol if none was selected
+    pub fn validate_genesis(&self) -> Result<(), CrunchError> {
+        let config = CONFIG.clone();
+        let genesis_hash = format!("{:?}", self.client().genesis_hash());
+        info!("{} network genesis hash {}", self.runtime, genesis_hash);
+
+        let expected_runtime = if config.chain_name.is_empty() {
+            self.runtime
+        } else {
+            SupportedRuntime::from(config.chain_name.as_str())
+        };
+        if genesis_hash != expected_runtime.genesis_hash() {
+            return Err(CrunchError::GenesisMismatch(
+                expected_runtime.to_string(),
+                genesis_hash,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Verify that staking is available on the connected chain. The pallet is looked up
+    /// in the metadata fetched from the node, rather than assumed for each network, so
+    /// that a chain where staking has been migrated to Asset Hub is detected
+    pub fn validate_staking(&self) -> Result<(), CrunchError> {
+        if self.client().metadata().pallet_by_name("Staking").is_none() {
+            return Err(CrunchError::StakingNotAvailable(self.runtime.to_string()));
+        }
+        Ok(())
+    }
+
+    /// Returns true if the utility pallet of the connected runtime supports `force_batch`
+    pub fn is_force_batch_available(&self) -> bool {
+        self.client()
+            .metadata()
+            .pallet_by_name("Utility")
+            .and_then(|pallet| pallet.call_variant_by_name("force_batch"))
+            .is_some()
+    }
+
+    /// Verify that batch calls are available on the connected chain. Runtimes without
+    /// `force_batch` fall back to `batch`, which is interrupted at the first failing call
+    pub fn validate_batch(&self) -> Result<(), CrunchError> {
+        if self.is_force_batch_available() {
+            return Ok(());
+        }
+        let is_batch_available = self
+            .client()
+            .metadata()
+            .pallet_by_name("Utility")
+            .and_then(|pallet| pallet.call_variant_by_name("batch"))
+            .is_some();
+        if !is_batch_available {
+            return Err(CrunchError::BatchNotAvailable(self.runtime.to_string()));
+        }
+        warn!("Utility force_batch not available on the {} network, calls are submitted with batch instead", self.runtime);
+        Ok(())
+    }
+
+    /// Returns the maximum number of blocks a transaction can be mortal for, which is
+    /// bounded by the number of block hashes kept by the chain (`System::BlockHashCount`)
+    fn max_tx_mortal_period(&self) -> Option<u64> {
+        let block_hash_count_addr = subxt::dynamic::constant("System", "BlockHashCount");
+        self.client()
+            .constants()
+            .at(&block_hash_count_addr)
+            .ok()
+            .and_then(|thunk| thunk.to_value().ok())
+            .and_then(|value| value.as_u128())
+            .map(|block_hash_count| block_hash_count as u64)
+    }
+
+    /// Returns the number of blocks transactions are mortal for defined by the user,
+    /// clamped to the maximum mortality supported by the connected chain
+    pub fn tx_mortal_period(&self) -> u64 {
+        let config = CONFIG.clone();
+        match self.max_tx_mortal_period() {
+            Some(max) if config.tx_mortal_period > max => max,
+            _ => config.tx_mortal_period,
+        }
+    }
+
+    /// Verify that the transaction mortal period is within the range supported by the
+    /// connected chain, so that transactions are not rejected mid-run
+    pub fn validate_tx_mortal_period(&self) -> Result<(), CrunchError> {
+        let config = CONFIG.clone();
+        if config.tx_mortal_period == 0 {
+            return Ok(());
+        }
+        if config.tx_mortal_period < MIN_TX_MORTAL_PERIOD {
+            warn!(
+                "Transaction mortal period of {} blocks is below the minimum of {} blocks, transactions are mortal for {} blocks instead",
+                config.tx_mortal_period, MIN_TX_MORTAL_PERIOD, MIN_TX_MORTAL_PERIOD
+            );
+        }
+        if let Some(max) = self.max_tx_mortal_period() {
+            if config.tx_mortal_period > max {
+                warn!(
+                    "Transaction mortal period of {} blocks exceeds the maximum of {} blocks supported by the {} network, transactions are mortal for {} blocks instead",
+                    config.tx_mortal_period, max, self.runtime, max
+                );
+            }
+        }
+        Ok(())
+    }
+
     pub fn people_client(&self) -> &Option<OnlineClient<SubstrateConfig>> {
         &self.people_client_option
     }
 
+    /// Fetch a storage entry from the people chain at the latest block. While the
+    /// people RPC connection is being reestablished the query is retried, so that
+    /// a transient disconnection does not fail the whole run
+    pub async fn fetch_people_storage<Addr>(
+        &self,
+        address: &Addr,
+    ) -> Result<Option<Addr::Target>, CrunchError>
+    where
+        Addr: Address<IsFetchable = Yes>,
+    {
+        let api = match self.people_client() {
+            Some(api) => api,
+            None => return Ok(None),
+        };
+        let mut retries = 0;
+        loop {
+            let result =
+                async { api.storage().at_latest().await?.fetch(address).await }.await;
+            match result {
+                Ok(value) => return Ok(value),
+                Err(e)
+                    if e.is_disconnected_will_reconnect()
+                        && retries < PEOPLE_CLIENT_MAX_RETRIES =>
+                {
+                    retries += 1;
+                    warn!(
+                        "The People chain RPC connection was dropped, retry {} of {}",
+                        retries, PEOPLE_CLIENT_MAX_RETRIES
+                    );
+                    task::sleep(time::Duration::from_secs(2 * retries as u64)).await;
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+
+    /// Returns the storage at the block hash given or at the latest block
+    pub async fn storage_at(
+        &self,
+        block_hash: Option<H256>,
+    ) -> Result<Storage<SubstrateConfig, OnlineClient<SubstrateConfig>>, CrunchError>
+    {
+        match block_hash {
+            Some(block_hash) => Ok(self.client().storage().at(block_hash)),
+            None => Ok(self.client().storage().at_latest().await?),
+        }
+    }
+
+    /// Fetch two storage entries at the block hash given or at the latest finalized
+    /// block in a single `state_queryStorageAt` request, so that entries read together
+    /// cost one round trip. If the request is not supported by the backend, each entry
+    /// is fetched on its own
+    pub async fn fetch_storage_pair<A, B>(
+        &self,
+        a: &A,
+        b: &B,
+        block_hash: Option<H256>,
+    ) -> Result<(Option<A::Target>, Option<B::Target>), CrunchError>
+    where
+        A: Address<IsFetchable = Yes>,
+        B: Address<IsFetchable = Yes>,
+        A::Target: Decode,
+        B::Target: Decode,
+    {
+        let storage = self.client().storage();
+        let keys = [storage.address_bytes(a)?, storage.address_bytes(b)?];
+        let block_hash = match block_hash {
+            Some(block_hash) => block_hash,
+            None => self.rpc().chain_get_finalized_head().await?,
+        };
+        match self
+            .rpc()
+            .state_query_storage_at(keys.iter().map(|key| &key[..]), Some(block_hash))
+            .await
+        {
+            Ok(change_sets) => {
+                let value = |key: &[u8]| {
+                    change_sets
+                        .iter()
+                        .flat_map(|change_set| change_set.changes.iter())
+                        .find(|(k, _)| k.0 == key)
+                        .and_then(|(_, data)| data.as_ref().map(|data| data.0.clone()))
+                };
+                let a_value = value(&keys[0])
+                    .map(|data| A::Target::decode(&mut &data[..]))
+                    .transpose()?;
+                let b_value = value(&keys[1])
+                    .map(|data| B::Target::decode(&mut &data[..]))
+                    .transpose()?;
+                Ok((a_value, b_value))
+            }
+            Err(e) => {
+                debug!(
+                    "state_queryStorageAt not available, entries fetched one by one: {}",
+                    e
+                );
+                let storage = storage.at(block_hash);
+                Ok((storage.fetch(a).await?, storage.fetch(b).await?))
+            }
+        }
+    }
+
+    /// Await the next item of a subscription. If `keepalive_seconds` is set and no item
+    /// is received within it, a lightweight RPC call is sent to keep the connection alive,
+    /// so that a silently dropped connection is detected before the next era
+    pub async fn next_or_keep_alive<S>(
+        &self,
+        subscription: &mut S,
+    ) -> Result<Option<S::Item>, CrunchError>
+    where
+        S: Stream + Unpin,
+    {
+        let config = CONFIG.clone();
+        if config.keepalive_seconds == 0 {
+            return Ok(subscription.next().await);
+        }
+        loop {
+            let keepalive = time::Duration::from_secs(config.keepalive_seconds);
+            match future::timeout(keepalive, subscription.next()).await {
+                Ok(item) => return Ok(item),
+                Err(_) => {
+                    debug!(
+                        "No item received in the last {} seconds, keep-alive sent",
+                        config.keepalive_seconds
+                    );
+                    match self.rpc().system_chain().await {
+                        Ok(_) => {}
+                        Err(e) if e.is_disconnected_will_reconnect() => {
+                            warn!(
+                                "The RPC connection was dropped will try to reconnect."
+                            );
+                        }
+                        Err(e) => return Err(e.into()),
+                    }
+                }
+            }
+        }
+    }
+
     pub fn rpc(&self) -> &LegacyRpcMethods<SubstrateConfig> {
         &self.rpc
     }
@@ -359,6 +983,37 @@ impl Crunch {
         Ok(())
     }
 
+    /// Send alert to the alert room if defined by the user, otherwise to private and
+    /// public rooms
+    pub async fn send_alert(
+        &self,
+        message: &str,
+        formatted_message: &str,
+    ) -> Result<(), CrunchError> {
+        self.matrix().send_alert(message, formatted_message).await?;
+        Ok(())
+    }
+
+    /// Send report to private and public rooms. If enabled by the user, the public room
+    /// receives a redacted version of the report
+    pub async fn send_report(&self, report: &Report) -> Result<(), CrunchError> {
+        let config = CONFIG.clone();
+        if config.matrix_public_room_redacted_enabled {
+            self.matrix()
+                .send_private_and_public_message(
+                    &report.message(),
+                    &report.formatted_message(),
+                    &report.public_message(),
+                    &report.public_formatted_message(),
+                )
+                .await?;
+        } else {
+            self.send_message(&report.message(), &report.formatted_message())
+                .await?;
+        }
+        Ok(())
+    }
+
     /// Spawn and restart crunch flakes task on error
     pub fn flakes() {
         spawn_and_restart_crunch_flakes_on_error();
@@ -379,7 +1034,46 @@ impl Crunch {
         spawn_crunch_once();
     }
 
+    /// Spawn crunch notification test task
+    pub fn notify_test() {
+        spawn_crunch_notify_test();
+    }
+
+    /// Spawn crunch pending task
+    pub fn pending() {
+        spawn_crunch_pending();
+    }
+
+    /// Spawn crunch dump metadata task
+    pub fn dump_metadata() {
+        spawn_crunch_dump_metadata();
+    }
+
+    /// Fetch the runtime metadata of the connected node and write it SCALE encoded
+    /// to the file defined by CRUNCH_DUMP_METADATA_PATH
+    async fn try_dump_metadata(&self) -> Result<(), CrunchError> {
+        let config = CONFIG.clone();
+        let path = if config.dump_metadata_path.is_empty() {
+            format!("{}_metadata.scale", self.runtime.to_string().to_lowercase())
+        } else {
+            config.dump_metadata_path.clone()
+        };
+        let runtime_version = self.rpc().state_get_runtime_version(None).await?;
+        let metadata = self.rpc().state_get_metadata(None).await?.into_raw();
+        fs::write(&path, &metadata)?;
+        info!(
+            "{} runtime metadata (spec version {}, {} bytes) written to {}",
+            self.runtime,
+            runtime_version.spec_version,
+            metadata.len(),
+            path
+        );
+        Ok(())
+    }
+
     async fn inspect(&self) -> Result<(), CrunchError> {
+        self.validate_genesis()?;
+        self.validate_staking()?;
         match self.runtime {
             SupportedRuntime::Polkadot => polkadot::inspect(self).await,
             SupportedRuntime::Kusama => kusama::inspect(self).await,
@@ -389,7 +1083,23 @@ impl Crunch {
         }
     }
 
+    async fn pending_summaries(&self) -> Result<Vec<StashSummary>, CrunchError> {
+        self.validate_genesis()?;
+        self.validate_staking()?;
+        match self.runtime {
+            SupportedRuntime::Polkadot => polkadot::pending(self).await,
+            SupportedRuntime::Kusama => kusama::pending(self).await,
+            SupportedRuntime::Paseo => paseo::pending(self).await,
+            SupportedRuntime::Westend => westend::pending(self).await,
+            // _ => unreachable!(),
+        }
+    }
+
     async fn try_run_batch(&self) -> Result<(), CrunchError> {
+        self.validate_genesis()?;
+        self.validate_staking()?;
+        self.validate_batch()?;
+        self.validate_tx_mortal_period()?;
         match self.runtime {
             SupportedRuntime::Polkadot => polkadot::try_crunch(self).await,
             SupportedRuntime::Kusama => kusama::try_crunch(self).await,
@@ -400,6 +1110,10 @@ impl Crunch {
     }
 
     async fn run_and_subscribe_era_paid_events(&self) -> Result<(), CrunchError> {
+        self.validate_genesis()?;
+        self.validate_staking()?;
+        self.validate_batch()?;
+        self.validate_tx_mortal_period()?;
         match self.runtime {
             SupportedRuntime::Polkadot => {
                 polkadot::run_and_subscribe_era_paid_events(self).await
@@ -429,10 +1143,11 @@ fn spawn_and_restart_subscription_on_error() {
                     CrunchError::MatrixError(_) => warn!("Matrix message skipped!"),
                     _ => {
                         error!("{}", e);
+                        metrics::add_error(&e.to_string());
                         let sleep_min = u32::pow(config.error_interval, n);
                         let message = format!("On hold for {} min!", sleep_min);
                         let formatted_message = format!("<br/>🚨 An error was raised -> <code>crunch</code> on hold for {} min while rescue is on the way 🚁 🚒 🚑 🚓<br/><br/>", sleep_min);
-                        c.send_message(&message, &formatted_message).await.unwrap();
+                        c.send_alert(&message, &formatted_message).await.unwrap();
                         thread::sleep(time::Duration::from_secs((60 * sleep_min).into()));
                         n += 1;
                         continue;
@@ -457,9 +1172,10 @@ fn spawn_and_restart_crunch_flakes_on_error() {
                     CrunchError::MatrixError(_) => warn!("Matrix message skipped!"),
                     _ => {
                         error!("{}", e);
+                        metrics::add_error(&e.to_string());
                         let message = format!("On hold for {} min!", sleep_min);
                         let formatted_message = format!("<br/>🚨 An error was raised -> <code>crunch</code> on hold for {} min while rescue is on the way 🚁 🚒 🚑 🚓<br/><br/>", sleep_min);
-                        c.send_message(&message, &formatted_message).await.unwrap();
+                        c.send_alert(&message, &formatted_message).await.unwrap();
                     }
                 }
                 thread::sleep(time::Duration::from_secs((60 * sleep_min).into()));
@@ -492,9 +1208,217 @@ fn spawn_crunch_once() {
     task::block_on(crunch_task);
 }
 
-pub fn random_wait(max: u64) -> u64 {
+/// Exits with code 0 if there are no unclaimed pages, 1 if there are unclaimed pages
+/// or 2 if the scan could not be completed
+fn spawn_crunch_pending() {
+    let crunch_task = task::spawn(async {
+        let c: Crunch = Crunch::new().await;
+        match c.pending_summaries().await {
+            Ok(summaries) => {
+                let unclaimed: usize = summaries.iter().map(|s| s.unclaimed).sum();
+                let stashes = summaries.iter().filter(|s| s.unclaimed > 0).count();
+                println!("{}", unclaimed);
+                info!(
+                    "{} unclaimed pages pending from {} stashes",
+                    unclaimed, stashes
+                );
+                if unclaimed > 0 {
+                    1
+                } else {
+                    0
+                }
+            }
+            Err(e) => {
+                error!("{}", e);
+                2
+            }
+        }
+    });
+    std::process::exit(task::block_on(crunch_task));
+}
+
+/// Exits with code 1 if the metadata could not be written
+fn spawn_crunch_dump_metadata() {
+    let crunch_task = task::spawn(async {
+        let c: Crunch = Crunch::new().await;
+        match c.try_dump_metadata().await {
+            Ok(()) => 0,
+            Err(e) => {
+                error!("{}", e);
+                1
+            }
+        }
+    });
+    std::process::exit(task::block_on(crunch_task));
+}
+
+fn spawn_crunch_notify_test() {
+    let crunch_task = task::spawn(async {
+        let config = CONFIG.clone();
+        let message = "crunch notification test ✅";
+
+        // NOTE: Each channel is tested independently so that a failure in one
+        // does not prevent the others from being tested
+        let mut results: Vec<(&str, Result<(), CrunchError>)> = Vec::new();
+        if config.matrix_disabled {
+            info!("Matrix notifications disabled, test skipped");
+        } else {
+            results.push(("Matrix", try_notify_test_matrix(message).await));
+        }
+
+        let mut failed = false;
+        for (channel, result) in results {
+            match result {
+                Ok(()) => info!("{} notification test succeeded ✅", channel),
+                Err(e) => {
+                    error!("{} notification test failed ❌ {}", channel, e);
+                    failed = true;
+                }
+            }
+        }
+        failed
+    });
+    if task::block_on(crunch_task) {
+        std::process::exit(1);
+    }
+}
+
+async fn try_notify_test_matrix(message: &str) -> Result<(), CrunchError> {
+    let config = CONFIG.clone();
+    // The runtime defines the public room to be joined, if not set by the user
+    // it is detected from the connected chain
+    let runtime = if config.chain_name.is_empty() {
+        let (_, _, runtime) = create_or_await_substrate_node_client().await;
+        runtime
+    } else {
+        SupportedRuntime::from(config.chain_name.as_str())
+    };
+    let mut matrix: Matrix = Matrix::new();
+    matrix.authenticate(runtime).await?;
+    matrix.send_private_message(message, message).await?;
+    Ok(())
+}
+
+/// Returns the range of calls to be sent in the next batch, starting at `start` and
+/// clamped to the number of calls available. A batch always holds at least one call.
+pub fn next_batch_range(start: usize, size: usize, len: usize) -> Range<usize> {
+    let start = cmp::min(start, len);
+    start..cmp::min(start.saturating_add(cmp::max(size, 1)), len)
+}
+
+/// Returns the maximum number of payouts defined by the user for specific stashes
+pub fn get_maximum_payouts_overrides() -> Result<HashMap<AccountId32, u32>, CrunchError> {
+    let config = CONFIG.clone();
+    parse_maximum_payouts_overrides(&config.maximum_payouts_overrides)
+        .map_err(CrunchError::Other)?
+        .into_iter()
+        .map(|(stash_str, maximum_payouts)| {
+            let stash = AccountId32::from_str(&stash_str).map_err(|e| {
+                CrunchError::Other(format!("Invalid account: {stash_str} error: {e:?}"))
+            })?;
+            Ok((stash, maximum_payouts))
+        })
+        .collect()
+}
+
+/// Returns true if payouts and messages should be grouped by main identity, either because
+/// it is enabled by the user or because the number of distinct identities exceeds the threshold
+pub fn is_group_identity_engaged(distinct_identities: usize) -> bool {
+    let config = CONFIG.clone();
+    if config.group_identity_enabled {
+        return true;
+    }
+    config.group_identity_threshold > 0
+        && distinct_identities > config.group_identity_threshold as usize
+}
+
+/// Returns a random number of seconds between min (inclusive) and max (exclusive)
+pub fn random_wait(min: u64, max: u64) -> u64 {
+    if min >= max {
+        return min;
+    }
     let mut rng = rand::thread_rng();
-    rng.gen_range(0..max)
+    rng.gen_range(min..max)
+}
+
+/// Returns the hash of the block number or block hash defined by the user in `at_block`
+pub async fn try_fetch_block_hash_from_config(
+    crunch: &Crunch,
+) -> Result<Option<H256>, CrunchError> {
+    let config = CONFIG.clone();
+    if config.at_block.is_empty() {
+        return Ok(None);
+    }
+
+    if config.at_block.starts_with("0x") {
+        let block_hash = H256::from_str(&config.at_block).map_err(|e| {
+            CrunchError::Other(format!(
+                "Invalid block hash: {} error: {e:?}",
+                config.at_block
+            ))
+        })?;
+        return Ok(Some(block_hash));
+    }
+
+    let block_number = config.at_block.parse::<u32>().map_err(|e| {
+        CrunchError::Other(format!(
+            "Invalid block number: {} error: {e:?}",
+            config.at_block
+        ))
+    })?;
+    match crunch
+        .rpc()
+        .chain_get_block_hash(Some(block_number.into()))
+        .await?
+    {
+        Some(block_hash) => Ok(Some(block_hash)),
+        None => Err(CrunchError::Other(format!(
+            "Block number {} not found",
+            block_number
+        ))),
+    }
+}
+
+/// Returns the era (exclusive) where the unclaimed rewards scan ends, excluding the
+/// most recent eras defined by CRUNCH_SKIP_RECENT_ERAS
+pub fn get_era_index_end(era_index: EraIndex) -> EraIndex {
+    let config = CONFIG.clone();
+    era_index.saturating_sub(config.skip_recent_eras)
+}
+
+/// Returns true if the sentinel file defined by CRUNCH_PAUSE_FILE exists
+pub fn is_paused() -> bool {
+    let config = CONFIG.clone();
+    if config.pause_file.is_empty() {
+        return false;
+    }
+    if std::path::Path::new(&config.pause_file).exists() {
+        info!("Paused, remove {} to resume", config.pause_file);
+        return true;
+    }
+    false
+}
+
+/// Returns true if the previous run completed less than `min_run_interval_seconds` ago
+pub fn is_within_min_run_interval(latest_run: Option<time::Instant>) -> bool {
+    let config = CONFIG.clone();
+    if config.min_run_interval_seconds == 0 {
+        return false;
+    }
+    match latest_run {
+        Some(instant) => {
+            let elapsed = instant.elapsed().as_secs();
+            if elapsed < config.min_run_interval_seconds {
+                debug!(
+                    "Skip run, previous run completed {} seconds ago (min interval {} seconds)",
+                    elapsed, config.min_run_interval_seconds
+                );
+                return true;
+            }
+            false
+        }
+        None => false,
+    }
 }
 
 pub async fn try_fetch_stashes_from_remote_url(
@@ -528,6 +1452,160 @@ pub async fn try_fetch_stashes_from_remote_url(
     Ok(Some(v))
 }
 
+/// Selects the window of stashes to be processed in this run if `max_stashes_per_run`
+/// is set, and persists the rotation cursor so that the next run continues from there
+pub fn rotate_stashes_window(
+    stashes: Vec<String>,
+) -> Result<(Vec<String>, Option<StashesWindow>), CrunchError> {
+    let config = CONFIG.clone();
+    let size = config.max_stashes_per_run as usize;
+    if size == 0 || stashes.len() <= size {
+        return Ok((stashes, None));
+    }
+
+    let mut state = State::load();
+    let (indexes, next_cursor) =
+        state::stashes_window(stashes.len(), size, state.stashes_cursor);
+    let window = StashesWindow {
+        start: indexes[0],
+        size: indexes.len(),
+        total: stashes.len(),
+    };
+    let selected: Vec<String> = indexes.iter().map(|&i| stashes[i].clone()).collect();
+    info!(
+        "{} of {} stashes selected in rotation, starting at position {}",
+        window.size,
+        window.total,
+        window.start + 1
+    );
+
+    state.stashes_cursor = next_cursor;
+    state.save()?;
+
+    Ok((selected, Some(window)))
+}
+
+/// Warns about the eras with no record in the era log since the last run, which
+/// indicates that crunch was not running when those eras were completed
+pub fn check_era_log_gaps(latest_era: EraIndex) {
+    let config = CONFIG.clone();
+    if config.era_log_size == 0 {
+        return;
+    }
+    let missing = State::load().missing_eras(latest_era);
+    if let (Some(first), Some(last)) = (missing.first(), missing.last()) {
+        warn!(
+            "Eras {}..{} have no record in the era log, crunch might have been down. Unclaimed eras within the scanned history are claimed in this run.",
+            first, last
+        );
+    }
+}
+
+/// Records the payouts submitted for the era in the era log, if enabled by the user
+pub fn record_era_processed(
+    era: EraIndex,
+    validators: &Validators,
+    summary: &PayoutSummary,
+) {
+    let config = CONFIG.clone();
+    if config.era_log_size == 0 {
+        return;
+    }
+    let record = EraRecord {
+        era,
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default(),
+        validators_paid: validators.iter().filter(|v| !v.payouts.is_empty()).count()
+            as u32,
+        calls_succeeded: summary.calls_succeeded,
+        calls_failed: summary.calls_failed,
+    };
+    let mut state = State::load();
+    state.record_era(record, config.era_log_size as usize);
+    // NOTE: Payouts have already been submitted at this point, so a failure is only logged
+    if let Err(e) = state.save() {
+        warn!("Era {} could not be recorded in the era log: {}", era, e);
+    }
+}
+
+/// Persists the run in the state file and returns the previous run of the same identity
+/// group, if the comparison with the previous run is enabled by the user
+pub fn swap_previous_run(
+    group: &str,
+    validators: &Validators,
+    summary: &PayoutSummary,
+) -> Option<RunRecord> {
+    let config = CONFIG.clone();
+    if !config.report_compare_previous_run {
+        return None;
+    }
+    let mut state = State::load();
+    let previous = state
+        .previous_runs
+        .insert(group.to_string(), RunRecord::from_run(validators, summary));
+    // NOTE: Payouts have already been submitted at this point, so a failure is only logged
+    if let Err(e) = state.save() {
+        warn!("Run could not be recorded in the state file: {}", e);
+    }
+    previous
+}
+
+/// Returns the pool members due to be compounded, skipping the members compounded less
+/// than `pool_compound_min_interval_eras` eras ago, as recorded in the state file
+pub fn filter_pool_members_due(
+    members: Vec<AccountId32>,
+    era: EraIndex,
+) -> Vec<AccountId32> {
+    let config = CONFIG.clone();
+    if config.pool_compound_min_interval_eras == 0 {
+        return members;
+    }
+    let state = State::load();
+    members
+        .into_iter()
+        .filter(|member| {
+            let is_due = state.is_pool_member_due(
+                &member.to_string(),
+                era,
+                config.pool_compound_min_interval_eras,
+            );
+            if !is_due {
+                debug!(
+                    "{} * compounded less than {} eras ago, member skipped",
+                    member, config.pool_compound_min_interval_eras
+                );
+            }
+            is_due
+        })
+        .collect()
+}
+
+/// Records the era the pool members were compounded in, if a minimum interval between
+/// compounds is set by the user
+pub fn record_pool_members_compounded(members: &[AccountId32], era: EraIndex) {
+    let config = CONFIG.clone();
+    if config.pool_compound_min_interval_eras == 0 || members.is_empty() {
+        return;
+    }
+    let mut state = State::load();
+    for member in members {
+        state.record_pool_member_compounded(
+            member.to_string(),
+            era,
+            config.pool_compound_min_interval_eras,
+        );
+    }
+    // NOTE: Rewards have already been compounded at this point, so a failure is only logged
+    if let Err(e) = state.save() {
+        warn!(
+            "Pool members compounded could not be recorded in the state file: {}",
+            e
+        );
+    }
+}
+
 #[derive(Deserialize, Clone, Debug)]
 pub struct OnetData {
     pub address: String,
@@ -546,8 +1624,9 @@ pub async fn try_fetch_onet_data(
         return Ok(None);
     }
 
+    // NOTE: {network} placeholder in a custom url is replaced by the network name
     let endpoint = if !config.onet_api_url.is_empty() {
-        config.onet_api_url
+        config.onet_api_url.replace("{network}", &chain_name)
     } else {
         format!("https://{}-onet-api.turboflakes.io", chain_name)
     };
@@ -586,8 +1665,264 @@ pub async fn try_fetch_onet_data(
     Ok(None)
 }
 
+/// Fetch the token price in the fiat currency defined by the user.
+/// Any failure is non-fatal and the fiat annotation is simply omitted from the report.
+pub async fn try_fetch_token_price(
+    chain_name: String,
+) -> Result<Option<f64>, CrunchError> {
+    let config = CONFIG.clone();
+    if config.fiat_currency.is_empty() {
+        return Ok(None);
+    }
+
+    let id = chain_name.to_lowercase();
+    let currency = config.fiat_currency.to_lowercase();
+    let url = format!(
+        "{}?ids={}&vs_currencies={}",
+        config.price_api_url, id, currency
+    );
+
+    debug!("Crunch <> token price loaded from {}", url);
+    match reqwest::get(&url).await {
+        Ok(response) => {
+            match response.status() {
+                reqwest::StatusCode::OK => {
+                    match response
+                        .json::<HashMap<String, HashMap<String, f64>>>()
+                        .await
+                    {
+                        Ok(parsed) => {
+                            let price = parsed
+                                .get(&id)
+                                .and_then(|prices| prices.get(&currency))
+                                .copied();
+                            if price.is_none() {
+                                warn!(
+                                    "Token price for {} in {} not available",
+                                    id, currency
+                                );
+                            }
+                            return Ok(price);
+                        }
+                        Err(e) => {
+                            error!("Unable to parse token price response error: {:?}", e)
+                        }
+                    };
+                }
+                other => {
+                    warn!("Unexpected code {:?} from price url {}", other, url);
+                }
+            };
+        }
+        Err(e) => error!("{:?}", e),
+    };
+    Ok(None)
+}
+
+/// Retries the query on transient RPC errors, so that a momentary glitch during the
+/// unclaimed eras scan does not waste the whole scan
+pub async fn with_scan_retries<T, F, Fut>(mut query: F) -> Result<T, CrunchError>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T, CrunchError>>,
+{
+    let mut retries = 0;
+    loop {
+        match query().await {
+            Ok(value) => return Ok(value),
+            Err(e) if e.is_transient() && retries < SCAN_MAX_RETRIES => {
+                retries += 1;
+                warn!(
+                    "Storage read failed ({}), retry {} of {}",
+                    e, retries, SCAN_MAX_RETRIES
+                );
+                task::sleep(time::Duration::from_secs(retries as u64)).await;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Logs the pallet, variant and fields of the event if CRUNCH_LOG_ALL_EVENTS_ENABLED (or its
+/// alias CRUNCH_DEBUG_LOG_ALL_EVENTS) is set
+pub fn log_event(event: &EventDetails<SubstrateConfig>) -> Result<(), CrunchError> {
+    let config = CONFIG.clone();
+    if config.log_all_events_enabled {
+        debug!(
+            "Event {}::{} {:?}",
+            event.pallet_name(),
+            event.variant_name(),
+            event.field_values()?
+        );
+    }
+    Ok(())
+}
+
+/// Returns the actual fee paid if the event is `TransactionPayment::TransactionFeePaid`
+///
+/// NOTE: The event is decoded dynamically since TransactionPayment events are not
+/// included in the static metadata files
+pub fn get_actual_fee_paid(
+    event: &EventDetails<SubstrateConfig>,
+) -> Result<Option<u128>, CrunchError> {
+    if event.pallet_name() != "TransactionPayment"
+        || event.variant_name() != "TransactionFeePaid"
+    {
+        return Ok(None);
+    }
+    if let Composite::Named(fields) = event.field_values()? {
+        for (name, value) in fields {
+            if name == "actual_fee" {
+                return Ok(value.as_u128());
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Returns the native token symbol from the system properties of the chain.
+/// NOTE: Multi-asset chains report an array of symbols, the first one being the native token
+pub fn get_token_symbol(properties: &SystemProperties) -> Option<String> {
+    match properties.get("tokenSymbol")? {
+        Value::String(symbol) => Some(symbol.to_string()),
+        Value::Array(symbols) => symbols.first()?.as_str().map(|s| s.to_string()),
+        _ => None,
+    }
+}
+
+/// Returns the native token decimals from the system properties of the chain.
+/// NOTE: Multi-asset chains report an array of decimals, the first one being the native token
+pub fn get_token_decimals(properties: &SystemProperties) -> Option<u8> {
+    let decimals = match properties.get("tokenDecimals")? {
+        Value::Number(decimals) => decimals.as_u64()?,
+        Value::Array(decimals) => decimals.first()?.as_u64()?,
+        _ => return None,
+    };
+    decimals.try_into().ok()
+}
+
+/// Returns the amount credited by a `Balances::Deposit` or `Balances::Minted` event
+pub fn get_balance_deposit(
+    event: &EventDetails<SubstrateConfig>,
+) -> Result<Option<u128>, CrunchError> {
+    if event.pallet_name() != "Balances"
+        || !["Deposit", "Minted"].contains(&event.variant_name())
+    {
+        return Ok(None);
+    }
+    if let Composite::Named(fields) = event.field_values()? {
+        for (name, value) in fields {
+            if name == "amount" {
+                return Ok(value.as_u128());
+            }
+        }
+    }
+    Ok(None)
+}
+
 pub fn get_account_id_from_storage_key(key: StorageKey) -> AccountId32 {
     let s = &key[key.len() - 32..];
     let v: [u8; 32] = s.try_into().expect("slice with incorrect length");
     v.into()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn batch_ranges(len: usize, size: usize) -> Vec<Range<usize>> {
+        let mut ranges = Vec::new();
+        let mut start = 0;
+        while start < len {
+            let range = next_batch_range(start, size, len);
+            start = range.end;
+            ranges.push(range);
+        }
+        ranges
+    }
+
+    // Alice keypair exported as a JSON keystore encrypted with the password "crunch"
+    const ALICE_KEYSTORE: &str = r#"{"encoded":"BwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcABAAAAQAAAAgAAAAJCQkJCQkJCQkJCQkJCQkJCQkJCQkJCQlirWPyKpT+6Fu6zTBfBctXZ0S3EknjkyPfRiqgcyEtOU8PBU/D8p93bhibRnnOCV5HZGJhIauJc4L2Ci/Rkk/B1o61TRsOA3NS3h5tTESAnZIc9a9zF9b61nNqA7WMBiMptt9CBNGUsRClTnivz9OAUGrNmE4sqREjXKaZAY/DLF5yoaZP","encoding":{"content":["pkcs8","sr25519"],"type":["scrypt","xsalsa20-poly1305"],"version":"3"},"address":"5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY","meta":{"name":"alice"}}"#;
+
+    fn write_seed_file(name: &str, data: &str) -> String {
+        let path = std::env::temp_dir().join(name);
+        fs::write(&path, data).unwrap();
+        path.to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn it_gets_a_keypair_from_a_json_keystore_seed_file() {
+        let path = write_seed_file("crunch_test_alice.json", ALICE_KEYSTORE);
+        let keypair = get_keypair_from_seed_file(&path, "crunch").unwrap();
+        assert_eq!(
+            keypair.account_id(),
+            AccountId32::from_str("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY")
+                .unwrap()
+        );
+        match keypair.sign(b"crunch") {
+            MultiSignature::Sr25519(signature) => assert!(sr25519::verify(
+                &sr25519::Signature(signature),
+                b"crunch",
+                &sr25519::PublicKey(keypair.account_id().0),
+            )),
+            _ => panic!("expected an sr25519 signature"),
+        }
+    }
+
+    #[test]
+    fn it_fails_to_decrypt_a_keystore_seed_file_with_a_wrong_password() {
+        let path = write_seed_file("crunch_test_alice.seed", ALICE_KEYSTORE);
+        let err = get_keypair_from_seed_file(&path, "wrong").err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "Other error: Keystore file could not be decrypted: invalid password"
+        );
+    }
+
+    #[test]
+    fn it_reads_native_token_from_system_properties() {
+        // scalar properties
+        let properties: SystemProperties = serde_json::from_str(
+            r#"{"ss58Format": 0, "tokenDecimals": 10, "tokenSymbol": "DOT"}"#,
+        )
+        .unwrap();
+        assert_eq!(get_token_symbol(&properties), Some("DOT".to_string()));
+        assert_eq!(get_token_decimals(&properties), Some(10));
+        // multi-asset properties take the first (native) entry
+        let properties: SystemProperties = serde_json::from_str(
+            r#"{"ss58Format": 2, "tokenDecimals": [12, 6], "tokenSymbol": ["KSM", "USDT"]}"#,
+        )
+        .unwrap();
+        assert_eq!(get_token_symbol(&properties), Some("KSM".to_string()));
+        assert_eq!(get_token_decimals(&properties), Some(12));
+        // missing or unexpected properties
+        let properties: SystemProperties =
+            serde_json::from_str(r#"{"tokenDecimals": [], "tokenSymbol": 1}"#).unwrap();
+        assert_eq!(get_token_symbol(&properties), None);
+        assert_eq!(get_token_decimals(&properties), None);
+        assert_eq!(get_token_symbol(&SystemProperties::new()), None);
+    }
+
+    #[test]
+    fn it_splits_calls_into_batch_ranges() {
+        // no calls
+        assert!(batch_ranges(0, 4).is_empty());
+        // zero size still makes progress one call at a time
+        assert_eq!(batch_ranges(3, 0), vec![0..1, 1..2, 2..3]);
+        // one call
+        assert_eq!(batch_ranges(1, 4), vec![0..1]);
+        assert_eq!(batch_ranges(3, 1), vec![0..1, 1..2, 2..3]);
+        // exactly one chunk
+        assert_eq!(batch_ranges(4, 4), vec![0..4]);
+        // remainder
+        assert_eq!(batch_ranges(10, 4), vec![0..4, 4..8, 8..10]);
+        // oversized batches never go past the calls available
+        assert_eq!(batch_ranges(2, usize::MAX), vec![0..2]);
+        assert_eq!(next_batch_range(usize::MAX, 1, 2), 2..2);
+        // same ranges as iterating by chunks
+        let calls: Vec<usize> = (0..10).collect();
+        for (range, chunk) in batch_ranges(10, 3).into_iter().zip(calls.chunks(3)) {
+            assert_eq!(&calls[range], chunk);
+        }
+    }
+}
@@ -0,0 +1,202 @@
+// The MIT License (MIT)
+// Copyright © 2021 Aukbit Ltd.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::config::CONFIG;
+use crate::metrics::{self, Metrics};
+use log::{debug, info, warn};
+use std::{
+    io::{Read, Write},
+    net::{TcpListener, TcpStream},
+    thread,
+};
+
+/// Renders a status page from the metrics collected, where `now` is the current Unix time
+pub fn render(metrics: &Metrics, now: u64) -> String {
+    let network = if metrics.network.is_empty() {
+        "-".to_string()
+    } else {
+        escape(&metrics.network)
+    };
+
+    let last_run = if metrics.runs == 0 {
+        "No run completed yet".to_string()
+    } else {
+        format!(
+            "Era {} processed {} ({} runs completed)",
+            metrics.last_era_processed,
+            describe_elapsed(now.saturating_sub(metrics.last_run_timestamp_seconds)),
+            metrics.runs
+        )
+    };
+
+    let last_summary = match &metrics.last_payout_summary {
+        Some((era, summary)) => format!(
+            "<tr><th>Last payout summary</th><td>Era {}: {} calls, {} succeeded, {} failed, {} plancks in fees</td></tr>",
+            era,
+            summary.calls,
+            summary.calls_succeeded,
+            summary.calls_failed,
+            summary.total_fees_paid
+        ),
+        None => String::new(),
+    };
+
+    let pending = if metrics.pending_unclaimed.is_empty() {
+        "<tr><td colspan=\"2\">No stashes scanned yet</td></tr>".to_string()
+    } else {
+        metrics
+            .pending_unclaimed
+            .iter()
+            .map(|(name, pages)| {
+                format!("<tr><td>{}</td><td>{}</td></tr>", escape(name), pages)
+            })
+            .collect::<Vec<String>>()
+            .join("")
+    };
+
+    let errors = if metrics.recent_errors.is_empty() {
+        "<li>No errors raised</li>".to_string()
+    } else {
+        metrics
+            .recent_errors
+            .iter()
+            .rev()
+            .map(|(timestamp, error)| {
+                format!(
+                    "<li>{}: <code>{}</code></li>",
+                    describe_elapsed(now.saturating_sub(*timestamp)),
+                    escape(error)
+                )
+            })
+            .collect::<Vec<String>>()
+            .join("")
+    };
+
+    format!(
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><meta http-equiv=\"refresh\" content=\"30\"><title>crunch · {network}</title></head><body><h1>crunch v{version}</h1><table><tr><th>Network</th><td>{network}</td></tr><tr><th>Active era</th><td>{active_era}</td></tr><tr><th>Signer balance</th><td>{balance} plancks</td></tr><tr><th>Last run</th><td>{last_run}</td></tr>{last_summary}</table><h2>Pending unclaimed pages</h2><table><tr><th>Stash</th><th>Pages</th></tr>{pending}</table><h2>Recent errors</h2><ul>{errors}</ul></body></html>\n",
+        network = network,
+        version = env!("CARGO_PKG_VERSION"),
+        active_era = metrics.active_era,
+        balance = metrics.signer_balance_plancks,
+        last_run = last_run,
+        last_summary = last_summary,
+        pending = pending,
+        errors = errors
+    )
+}
+
+fn describe_elapsed(seconds: u64) -> String {
+    match seconds {
+        0..=59 => format!("{}s ago", seconds),
+        60..=3599 => format!("{}min ago", seconds / 60),
+        _ => format!("{}h ago", seconds / 3600),
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Serves the status page on `CRUNCH_DASHBOARD_PORT` in a background thread
+pub fn spawn_dashboard_server() {
+    let config = CONFIG.clone();
+    let addr = format!("0.0.0.0:{}", config.dashboard_port);
+    let listener = match TcpListener::bind(&addr) {
+        Ok(listener) => listener,
+        Err(e) => {
+            warn!("Dashboard server could not be started on {}: {}", addr, e);
+            return;
+        }
+    };
+    info!("Dashboard available at http://{}", addr);
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    if let Err(e) = handle_connection(stream) {
+                        debug!("Dashboard request failed: {}", e);
+                    }
+                }
+                Err(e) => debug!("Dashboard connection failed: {}", e),
+            }
+        }
+    });
+}
+
+fn handle_connection(mut stream: TcpStream) -> std::io::Result<()> {
+    // NOTE: The request is not parsed, every path responds with the status page
+    let mut buffer = [0; 1024];
+    let _ = stream.read(&mut buffer)?;
+    let body = render(&metrics::snapshot(), metrics::now_seconds());
+    let response = format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes())?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::PayoutSummary;
+
+    #[test]
+    fn it_renders_dashboard() {
+        let mut metrics = Metrics::default();
+        metrics.network = "Polkadot".to_string();
+        metrics.active_era = 1501;
+        metrics.runs = 3;
+        metrics.last_era_processed = 1500;
+        metrics.last_run_timestamp_seconds = 1_000;
+        metrics.last_payout_summary = Some((
+            1500,
+            PayoutSummary {
+                calls: 4,
+                calls_succeeded: 4,
+                ..Default::default()
+            },
+        ));
+        metrics.pending_unclaimed = vec![("<ALICE>".to_string(), 2)];
+        metrics
+            .recent_errors
+            .push_back((1_060, "Connection lost".to_string()));
+
+        let html = render(&metrics, 1_120);
+        assert!(html.contains("<tr><th>Active era</th><td>1501</td></tr>"));
+        assert!(html.contains("Era 1500 processed 2min ago (3 runs completed)"));
+        assert!(html.contains("Era 1500: 4 calls, 4 succeeded, 0 failed"));
+        assert!(html.contains("<tr><td>&lt;ALICE&gt;</td><td>2</td></tr>"));
+        assert!(html.contains("<li>1min ago: <code>Connection lost</code></li>"));
+    }
+
+    #[test]
+    fn it_renders_dashboard_before_first_run() {
+        let html = render(&Metrics::default(), 1_000);
+        assert!(html.contains("No run completed yet"));
+        assert!(html.contains("No stashes scanned yet"));
+        assert!(html.contains("No errors raised"));
+    }
+}
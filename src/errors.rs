@@ -52,6 +52,18 @@ pub enum CrunchError {
     MatrixError(String),
     #[error("Subscription finished")]
     SubscriptionFinished,
+    #[error("Signer account {0} not found on the {1} network, verify the seed file and that the account is funded")]
+    SignerNotFound(String, String),
+    #[error("Connected chain genesis hash {1} does not match the {0} network, verify that the RPC endpoint points to the expected chain")]
+    GenesisMismatch(String, String),
+    #[error("Staking pallet not available on the {0} network, staking might have been migrated to Asset Hub which is not supported by this version")]
+    StakingNotAvailable(String),
+    #[error("Utility pallet batch calls not available on the {0} network, payouts can not be submitted")]
+    BatchNotAvailable(String),
+    #[error("Nomination pools [{0}] not found on the {1} network, verify the configured pool ids")]
+    PoolNotFound(String, String),
+    #[error("External signer error: {0}")]
+    ExternalSignerError(String),
     #[error("Reqwest error: {0}")]
     ReqwestError(#[from] reqwest::Error),
     #[error("ParseError error: {0}")]
@@ -66,6 +78,17 @@ pub enum CrunchError {
     Other(String),
 }
 
+impl CrunchError {
+    /// Returns true if the error is an RPC error that might not happen again if the
+    /// request is retried (e.g. a momentary disconnection or timeout)
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            CrunchError::SubxtError(subxt::Error::Rpc(_)) | CrunchError::RpcError(_)
+        )
+    }
+}
+
 /// Convert &str to CrunchError
 impl From<&str> for CrunchError {
     fn from(error: &str) -> Self {
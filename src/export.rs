@@ -0,0 +1,250 @@
+// The MIT License (MIT)
+// Copyright © 2021 Aukbit Ltd.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::config::CONFIG;
+use crate::errors::CrunchError;
+use crate::report::{
+    EraIndex, PageIndex, Payout, Points, RawData, StashSummary, Validator,
+};
+use log::{debug, warn};
+use serde::Serialize;
+use std::{collections::BTreeMap, fs::OpenOptions, io::Write, result::Result};
+
+/// Run data appended as a single JSON line to the file defined by `CRUNCH_EXPORT_PATH`
+#[derive(Serialize, Debug)]
+pub struct Export {
+    pub network: String,
+    pub active_era: EraIndex,
+    pub token_symbol: String,
+    pub token_decimals: u8,
+    pub signer: String,
+    pub calls: u32,
+    pub calls_succeeded: u32,
+    pub calls_failed: u32,
+    pub total_fees_paid: u128,
+    pub validators: Vec<ExportValidator>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ExportValidator {
+    pub stash: String,
+    pub name: String,
+    pub is_active: bool,
+    pub claimed: Vec<(EraIndex, PageIndex)>,
+    pub unclaimed: Vec<(EraIndex, PageIndex)>,
+    /// Pages dropped while scanning in era chunks, only counted
+    #[serde(skip_serializing_if = "is_zero")]
+    pub claimed_dropped: usize,
+    #[serde(skip_serializing_if = "is_zero")]
+    pub unclaimed_dropped: usize,
+    pub payouts: Vec<ExportPayout>,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ExportPayout {
+    pub era_index: EraIndex,
+    pub block_number: u32,
+    pub extrinsic: String,
+    pub validator_amount: u128,
+    pub nominators_amount: u128,
+    pub nominators_quantity: u32,
+    pub points: ExportPoints,
+    /// Amount rewarded to every nominator, only exported if `CRUNCH_EXPORT_NOMINATOR_DETAIL` is set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nominators: Option<BTreeMap<String, u128>>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ExportPoints {
+    pub validator: u32,
+    pub era_avg: f64,
+    pub ci99_9_interval: (f64, f64),
+    pub outlier_limits: (f64, f64),
+    /// Points of every validator in the era, only exported if `CRUNCH_EXPORT_RAW_POINTS` is set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub individual: Option<BTreeMap<String, u32>>,
+}
+
+impl From<&RawData> for Export {
+    fn from(data: &RawData) -> Export {
+        Export {
+            network: data.network.name.clone(),
+            active_era: data.network.active_era,
+            token_symbol: data.network.token_symbol.clone(),
+            token_decimals: data.network.token_decimals,
+            signer: data.signer_details.account.to_string(),
+            calls: data.payout_summary.calls,
+            calls_succeeded: data.payout_summary.calls_succeeded,
+            calls_failed: data.payout_summary.calls_failed,
+            total_fees_paid: data.payout_summary.total_fees_paid,
+            validators: data.validators.iter().map(ExportValidator::from).collect(),
+        }
+    }
+}
+
+fn is_zero(n: &usize) -> bool {
+    *n == 0
+}
+
+impl From<&Validator> for ExportValidator {
+    fn from(v: &Validator) -> ExportValidator {
+        ExportValidator {
+            stash: v.stash.to_string(),
+            name: v.name.clone(),
+            is_active: v.is_active,
+            claimed: v.claimed.clone(),
+            unclaimed: v.unclaimed.clone(),
+            claimed_dropped: v.claimed_dropped.pages,
+            unclaimed_dropped: v.unclaimed_dropped.pages,
+            payouts: v.payouts.iter().map(ExportPayout::from).collect(),
+            warnings: v.warnings.clone(),
+        }
+    }
+}
+
+impl From<&Payout> for ExportPayout {
+    fn from(p: &Payout) -> ExportPayout {
+        let config = CONFIG.clone();
+        ExportPayout {
+            era_index: p.era_index,
+            block_number: p.block_number,
+            extrinsic: format!("{:?}", p.extrinsic),
+            validator_amount: p.validator_amount_value,
+            nominators_amount: p.nominators_amount_value,
+            nominators_quantity: p.nominators_quantity,
+            points: ExportPoints::from(&p.points),
+            nominators: if config.export_nominator_detail {
+                Some(
+                    p.nominators
+                        .iter()
+                        .map(|(stash, amount)| (stash.to_string(), *amount))
+                        .collect(),
+                )
+            } else {
+                None
+            },
+        }
+    }
+}
+
+impl From<&Points> for ExportPoints {
+    fn from(p: &Points) -> ExportPoints {
+        let config = CONFIG.clone();
+        ExportPoints {
+            validator: p.validator,
+            era_avg: p.era_avg,
+            ci99_9_interval: p.ci99_9_interval,
+            outlier_limits: p.outlier_limits,
+            individual: if config.export_raw_points {
+                Some(
+                    p.individual
+                        .iter()
+                        .map(|(stash, points)| (stash.to_string(), *points))
+                        .collect(),
+                )
+            } else {
+                None
+            },
+        }
+    }
+}
+
+/// Rollup of `crunch view` printed as a single JSON line if `CRUNCH_VIEW_JSON_ENABLED` is set
+#[derive(Serialize, Debug)]
+pub struct ViewSummary {
+    pub history_depth: u32,
+    pub total_stashes: usize,
+    pub total_claimed: usize,
+    pub total_unclaimed: usize,
+    pub stashes: Vec<ViewStashSummary>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ViewStashSummary {
+    pub stash: String,
+    pub name: String,
+    pub claimed: usize,
+    pub unclaimed: usize,
+    pub oldest_unclaimed_era: Option<EraIndex>,
+}
+
+impl ViewSummary {
+    pub fn new(history_depth: u32, summaries: &Vec<StashSummary>) -> ViewSummary {
+        ViewSummary {
+            history_depth,
+            total_stashes: summaries.len(),
+            total_claimed: summaries.iter().map(|s| s.claimed).sum(),
+            total_unclaimed: summaries.iter().map(|s| s.unclaimed).sum(),
+            stashes: summaries.iter().map(ViewStashSummary::from).collect(),
+        }
+    }
+}
+
+impl From<&StashSummary> for ViewStashSummary {
+    fn from(s: &StashSummary) -> ViewStashSummary {
+        ViewStashSummary {
+            stash: s.stash.to_string(),
+            name: s.name.clone(),
+            claimed: s.claimed,
+            unclaimed: s.unclaimed,
+            oldest_unclaimed_era: s.oldest_unclaimed_era,
+        }
+    }
+}
+
+/// Returns the rollup of `crunch view` as a single JSON line
+pub fn view_summary_json(
+    history_depth: u32,
+    summaries: &Vec<StashSummary>,
+) -> Result<String, CrunchError> {
+    serde_json::to_string(&ViewSummary::new(history_depth, summaries))
+        .map_err(|e| CrunchError::Other(e.to_string()))
+}
+
+/// Appends the run data to the export file as a single JSON line
+pub fn append(data: &RawData) -> Result<(), CrunchError> {
+    let config = CONFIG.clone();
+    let line = serde_json::to_string(&Export::from(data))
+        .map_err(|e| CrunchError::Other(e.to_string()))?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&config.export_path)?;
+    writeln!(file, "{}", line)?;
+    debug!("Run data exported to {}", config.export_path);
+    Ok(())
+}
+
+/// Appends the run data to the export file if enabled by the user. Payouts have
+/// already been submitted at this point, so an export failure is only logged
+pub fn try_append(data: &RawData) {
+    let config = CONFIG.clone();
+    if !config.export_enabled {
+        return;
+    }
+    if let Err(e) = append(data) {
+        warn!(
+            "Run data could not be exported to {}: {}",
+            config.export_path, e
+        );
+    }
+}
@@ -21,17 +21,26 @@
 
 mod config;
 mod crunch;
+mod dashboard;
 mod errors;
+mod export;
 mod matrix;
+mod metrics;
 mod pools;
 mod report;
 mod runtimes;
+mod state;
 mod stats;
 
 use crate::config::{RunMode, CONFIG};
 use crate::crunch::Crunch;
-use log::info;
-use std::env;
+use env_logger::WriteStyle;
+use log::{debug, error, info};
+use std::{
+    env,
+    io::{self, IsTerminal},
+    process,
+};
 
 fn main() {
     let config = CONFIG.clone();
@@ -40,7 +49,20 @@ fn main() {
     } else {
         env::set_var("RUST_LOG", "crunch=info");
     }
-    env_logger::try_init().unwrap_or_default();
+    // Disable colors if requested or if the log output (stderr) is not a terminal,
+    // so that escape codes do not leak into journald or docker captured logs
+    let write_style = if config.no_color
+        || env::var_os("NO_COLOR").is_some()
+        || !io::stderr().is_terminal()
+    {
+        WriteStyle::Never
+    } else {
+        WriteStyle::Auto
+    };
+    env_logger::Builder::from_default_env()
+        .write_style(write_style)
+        .try_init()
+        .unwrap_or_default();
 
     info!(
         "{} v{} * {}",
@@ -49,10 +71,39 @@ fn main() {
         env!("CARGO_PKG_DESCRIPTION")
     );
 
+    // Log effective configuration with secrets redacted
+    debug!("{:?}", config);
+
     if config.only_view {
         return Crunch::view();
     }
 
+    if config.only_notify_test {
+        return Crunch::notify_test();
+    }
+
+    if config.only_pending {
+        return Crunch::pending();
+    }
+
+    if config.only_dump_metadata {
+        return Crunch::dump_metadata();
+    }
+
+    // Make sure the external signer signs for the expected account before any payout
+    if let Err(e) = crunch::verify_external_signer_from_config() {
+        error!("{}", e);
+        process::exit(1);
+    }
+
+    if config.metrics_enabled {
+        metrics::spawn_metrics_server();
+    }
+
+    if config.dashboard_port > 0 {
+        dashboard::spawn_dashboard_server();
+    }
+
     match config.run_mode {
         RunMode::Once => Crunch::once(),
         RunMode::Daily | RunMode::Turbo => Crunch::flakes(),
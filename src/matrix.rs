@@ -30,6 +30,9 @@ use std::{collections::HashMap, result::Result, thread, time};
 use url::form_urlencoded::byte_serialize;
 
 const MATRIX_URL: &str = "https://matrix.org/_matrix/client/r0";
+// NOTE: Matrix events are limited to 65536 bytes. Since the event carries both
+// the plain body and the formatted body, each one is kept below half of the limit
+const MATRIX_MESSAGE_MAX_SIZE: usize = 30000;
 
 type AccessToken = String;
 type RoomID = String;
@@ -147,6 +150,7 @@ pub struct Matrix {
     chain: SupportedRuntime,
     private_room_id: String,
     public_room_id: String,
+    alert_room_id: String,
     disabled: bool,
 }
 
@@ -158,6 +162,7 @@ impl Default for Matrix {
             chain: SupportedRuntime::Westend,
             private_room_id: String::from(""),
             public_room_id: String::from(""),
+            alert_room_id: String::from(""),
             disabled: false,
         }
     }
@@ -291,6 +296,16 @@ impl Matrix {
                 self.chain.public_room_alias()
             );
         }
+        // Join alert room if defined by the user
+        if !config.matrix_alert_room.is_empty() {
+            self.alert_room_id = self
+                .get_or_join_alert_room(&config.matrix_alert_room)
+                .await?;
+            info!(
+                "Alerts will be sent to room {} (Alert)",
+                config.matrix_alert_room
+            );
+        }
         // Change Crunch Bot display name
         if !config.matrix_bot_display_name_disabled {
             self.change_bot_display_name().await?;
@@ -490,27 +505,132 @@ impl Matrix {
         }
     }
 
+    /// Resolve the alert room alias or id and join it if not already a member
+    async fn get_or_join_alert_room(&self, room: &str) -> Result<RoomID, MatrixError> {
+        let room_id = if room.starts_with('#') {
+            match self.get_room_id_by_room_alias(room).await? {
+                Some(room_id) => room_id,
+                None => {
+                    return Err(MatrixError::Other(format!(
+                        "Alert room {} not found.",
+                        room
+                    )))
+                }
+            }
+        } else {
+            room.to_string()
+        };
+        let joined_rooms = self.get_joined_rooms().await?;
+        if !joined_rooms.contains(&room_id) {
+            self.join_room(&room_id).await?;
+        }
+        Ok(room_id)
+    }
+
     pub async fn send_message(
         &self,
         message: &str,
         formatted_message: &str,
+    ) -> Result<(), MatrixError> {
+        self.send_private_and_public_message(
+            message,
+            formatted_message,
+            message,
+            formatted_message,
+        )
+        .await
+    }
+
+    /// Send an alert to the alert room, or to the private and public rooms if no
+    /// alert room is defined
+    pub async fn send_alert(
+        &self,
+        message: &str,
+        formatted_message: &str,
+    ) -> Result<(), MatrixError> {
+        if self.disabled {
+            return Ok(());
+        }
+        if self.alert_room_id.is_empty() {
+            return self.send_message(message, formatted_message).await;
+        }
+        self.dispatch_split_message(&self.alert_room_id, message, formatted_message)
+            .await
+    }
+
+    /// Send a message to the private room only
+    pub async fn send_private_message(
+        &self,
+        message: &str,
+        formatted_message: &str,
+    ) -> Result<(), MatrixError> {
+        if self.disabled {
+            return Ok(());
+        }
+        self.dispatch_split_message(&self.private_room_id, message, formatted_message)
+            .await
+    }
+
+    /// Send a message to the private room and a different one to the public room
+    pub async fn send_private_and_public_message(
+        &self,
+        private_message: &str,
+        private_formatted_message: &str,
+        public_message: &str,
+        public_formatted_message: &str,
     ) -> Result<(), MatrixError> {
         if self.disabled {
             return Ok(());
         }
         let config = CONFIG.clone();
         // Send message to private room (private assigned to the matrix_username in config)
-        self.dispatch_message(&self.private_room_id, &message, &formatted_message)
-            .await?;
+        self.dispatch_split_message(
+            &self.private_room_id,
+            private_message,
+            private_formatted_message,
+        )
+        .await?;
         // Send message to public room (public room available for the connected chain)
         if !config.matrix_public_room_disabled {
-            self.dispatch_message(&self.public_room_id, &message, &formatted_message)
-                .await?;
+            self.dispatch_split_message(
+                &self.public_room_id,
+                public_message,
+                public_formatted_message,
+            )
+            .await?;
         }
 
         Ok(())
     }
 
+    async fn dispatch_split_message(
+        &self,
+        room_id: &str,
+        message: &str,
+        formatted_message: &str,
+    ) -> Result<(), MatrixError> {
+        // Split message if it exceeds the maximum size accepted by the homeserver
+        let formatted_messages =
+            split_formatted_message(formatted_message, MATRIX_MESSAGE_MAX_SIZE);
+        if formatted_messages.len() > 1 {
+            info!(
+                "Matrix message split into {} messages",
+                formatted_messages.len()
+            );
+        }
+        for formatted_message in formatted_messages.iter() {
+            // NOTE: plain body follows the same split as the formatted body
+            let message = if formatted_messages.len() > 1 {
+                strip_tags(formatted_message)
+            } else {
+                message.to_string()
+            };
+            self.dispatch_message(room_id, &message, &formatted_message)
+                .await?;
+        }
+        Ok(())
+    }
+
     #[async_recursion]
     async fn dispatch_message(
         &self,
@@ -568,3 +688,199 @@ impl Matrix {
         }
     }
 }
+
+/// Split a formatted message into messages no larger than `max` bytes.
+///
+/// Messages are split on empty lines, which separate each validator section in the
+/// report, so that a validator section is never broken in half. A section that is
+/// still larger than `max` is split line by line. If the message is wrapped into a
+/// `<details>` block each message is wrapped again with the same summary.
+fn split_formatted_message(formatted_message: &str, max: usize) -> Vec<String> {
+    if formatted_message.len() <= max {
+        return vec![formatted_message.to_string()];
+    }
+
+    // Unwrap <details><summary>..</summary> .. </details> if present
+    let (summary, content) = match (
+        formatted_message.starts_with("<details><summary>"),
+        formatted_message.find("</summary>"),
+    ) {
+        (true, Some(i)) => (
+            Some(&formatted_message["<details><summary>".len()..i]),
+            formatted_message[i + "</summary>".len()..].trim_end_matches("</details>"),
+        ),
+        _ => (None, formatted_message),
+    };
+
+    // Keep space for the <details> wrapper and the counter added to the summary
+    let wrapper_size = match summary {
+        Some(summary) => summary.len() + 64,
+        None => 0,
+    };
+    let max = max.saturating_sub(wrapper_size).max(1);
+
+    let mut messages: Vec<String> = Vec::new();
+    let mut message = String::new();
+    for section in content.split("<br><br>") {
+        // Sections too large are split by line, and lines too large are hard split
+        let mut parts: Vec<(&str, &str)> = Vec::new();
+        if section.len() > max {
+            for (i, line) in section.split("<br>").enumerate() {
+                for (j, piece) in hard_split(line, max).into_iter().enumerate() {
+                    let separator = match (i, j) {
+                        (0, 0) => "<br><br>",
+                        (_, 0) => "<br>",
+                        _ => "",
+                    };
+                    parts.push((separator, piece));
+                }
+            }
+        } else {
+            parts.push(("<br><br>", section));
+        }
+        for (separator, part) in parts {
+            if !message.is_empty() && message.len() + separator.len() + part.len() > max {
+                messages.push(message);
+                message = String::new();
+            }
+            if !message.is_empty() {
+                message.push_str(separator);
+            }
+            message.push_str(part);
+        }
+    }
+    if !message.is_empty() {
+        messages.push(message);
+    }
+
+    match summary {
+        Some(summary) => {
+            let n = messages.len();
+            messages
+                .into_iter()
+                .enumerate()
+                .map(|(i, message)| {
+                    format!(
+                        "<details><summary>{} ({}/{})</summary>{}</details>",
+                        summary,
+                        i + 1,
+                        n,
+                        message
+                    )
+                })
+                .collect()
+        }
+        None => messages,
+    }
+}
+
+/// Splits the text in pieces of at most `max` bytes, avoiding to break a tag in half
+fn hard_split(text: &str, max: usize) -> Vec<&str> {
+    let mut pieces = Vec::new();
+    let mut rest = text;
+    while rest.len() > max {
+        let mut end = max;
+        while !rest.is_char_boundary(end) {
+            end -= 1;
+        }
+        // Cut before a tag left open by the piece, if any
+        if let Some(open) = rest[..end].rfind('<') {
+            if open > 0 && !rest[open..end].contains('>') {
+                end = open;
+            }
+        }
+        if end == 0 {
+            end = rest.chars().next().map(|c| c.len_utf8()).unwrap_or(1);
+        }
+        pieces.push(&rest[..end]);
+        rest = &rest[end..];
+    }
+    pieces.push(rest);
+    pieces
+}
+
+/// Returns the plain text of the formatted message, with line breaks kept as new lines
+fn strip_tags(formatted_message: &str) -> String {
+    let text = formatted_message
+        .replace("<br>", "\n")
+        .replace("<br/>", "\n")
+        .replace("</summary>", "\n");
+    let mut plain = String::with_capacity(text.len());
+    let mut in_tag = false;
+    for c in text.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => plain.push(c),
+            _ => {}
+        }
+    }
+    plain
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_does_not_split_small_messages() {
+        let message = "<details><summary>Crunched</summary>a<br><br>b</details>";
+        assert_eq!(
+            split_formatted_message(message, 1000),
+            vec![message.to_string()]
+        );
+    }
+
+    #[test]
+    fn it_splits_large_messages_on_section_boundaries() {
+        let sections: Vec<String> = (0..10).map(|i| format!("validator {}", i)).collect();
+        let message = format!(
+            "<details><summary>Crunched</summary>{}</details>",
+            sections.join("<br><br>")
+        );
+        let messages = split_formatted_message(&message, 100);
+        assert!(messages.len() > 1);
+        for (i, m) in messages.iter().enumerate() {
+            assert!(m.len() <= 100);
+            assert!(m.starts_with(&format!(
+                "<details><summary>Crunched ({}/{})</summary>",
+                i + 1,
+                messages.len()
+            )));
+            assert!(m.ends_with("</details>"));
+        }
+        // no section is broken in half
+        for section in sections {
+            assert!(messages.iter().any(|m| m.contains(&section)));
+        }
+    }
+
+    #[test]
+    fn it_hard_splits_sections_larger_than_the_maximum_size() {
+        let section = format!("<b>{}</b>", "x".repeat(250));
+        let message = format!(
+            "<details><summary>Crunched</summary>a<br><br>{}</details>",
+            section
+        );
+        let messages = split_formatted_message(&message, 100);
+        assert!(messages.len() > 1);
+        for m in messages.iter() {
+            assert!(m.len() <= 100);
+        }
+        let content: String = messages
+            .iter()
+            .map(|m| m[m.find("</summary>").unwrap() + "</summary>".len()..].to_string())
+            .map(|m| m.trim_end_matches("</details>").to_string())
+            .collect();
+        // NOTE: the separator between messages is dropped
+        assert_eq!(content, format!("a{}", section));
+    }
+
+    #[test]
+    fn it_strips_tags_from_the_plain_body() {
+        assert_eq!(
+            strip_tags("<details><summary>Crunched (1/2)</summary><b>Alice</b><br>paid <code>0x12</code></details>"),
+            "Crunched (1/2)\nAlice\npaid 0x12"
+        );
+    }
+}
@@ -0,0 +1,262 @@
+// The MIT License (MIT)
+// Copyright © 2021 Aukbit Ltd.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::config::CONFIG;
+use crate::report::{EraIndex, PayoutSummary, Validator};
+use lazy_static::lazy_static;
+use log::{debug, info, warn};
+use std::{
+    collections::VecDeque,
+    io::{Read, Write},
+    net::{TcpListener, TcpStream},
+    sync::Mutex,
+    thread,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+// Maximum number of recent errors kept for the dashboard
+const MAX_RECENT_ERRORS: usize = 10;
+
+// Metrics collected during runs and exposed in the OpenMetrics text format
+lazy_static! {
+    static ref METRICS: Mutex<Metrics> = Mutex::new(Metrics::default());
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Metrics {
+    pub network: String,
+    pub runs: u64,
+    pub calls: u64,
+    pub calls_succeeded: u64,
+    pub calls_failed: u64,
+    pub fees_paid_plancks: u128,
+    /// Era of the latest payout summary, exposed as an exemplar of the calls counter
+    pub last_calls_era: Option<(EraIndex, u32)>,
+    pub last_run_timestamp_seconds: u64,
+    pub last_era_processed: EraIndex,
+    pub signer_balance_plancks: u128,
+    pub active_era: EraIndex,
+    /// Payout summary of the latest era processed, shown in the dashboard
+    pub last_payout_summary: Option<(EraIndex, PayoutSummary)>,
+    /// Number of pages left unclaimed per stash at the end of the latest run
+    pub pending_unclaimed: Vec<(String, usize)>,
+    /// Unix time and description of the latest errors raised, oldest first
+    pub recent_errors: VecDeque<(u64, String)>,
+}
+
+impl Metrics {
+    /// Renders the metrics in the OpenMetrics text format, labeled by network
+    pub fn render(&self) -> String {
+        let label = format!("{{network=\"{}\"}}", self.network.to_lowercase());
+        let calls_exemplar = match self.last_calls_era {
+            Some((era, calls)) => format!(" # {{era=\"{}\"}} {}", era, calls),
+            None => String::new(),
+        };
+        let lines = vec![
+            "# TYPE crunch_runs counter".to_string(),
+            "# HELP crunch_runs Number of runs completed.".to_string(),
+            format!("crunch_runs_total{} {}", label, self.runs),
+            "# TYPE crunch_payout_calls counter".to_string(),
+            "# HELP crunch_payout_calls Number of payout calls submitted.".to_string(),
+            format!(
+                "crunch_payout_calls_total{} {}{}",
+                label, self.calls, calls_exemplar
+            ),
+            "# TYPE crunch_payout_calls_succeeded counter".to_string(),
+            "# HELP crunch_payout_calls_succeeded Number of payout calls succeeded."
+                .to_string(),
+            format!(
+                "crunch_payout_calls_succeeded_total{} {}",
+                label, self.calls_succeeded
+            ),
+            "# TYPE crunch_payout_calls_failed counter".to_string(),
+            "# HELP crunch_payout_calls_failed Number of payout calls failed.".to_string(),
+            format!(
+                "crunch_payout_calls_failed_total{} {}",
+                label, self.calls_failed
+            ),
+            "# TYPE crunch_fees_paid_plancks counter".to_string(),
+            "# HELP crunch_fees_paid_plancks Transaction fees paid by the signer.".to_string(),
+            format!(
+                "crunch_fees_paid_plancks_total{} {}",
+                label, self.fees_paid_plancks
+            ),
+            "# TYPE crunch_last_run_timestamp_seconds gauge".to_string(),
+            "# HELP crunch_last_run_timestamp_seconds Unix time of the last completed run."
+                .to_string(),
+            format!(
+                "crunch_last_run_timestamp_seconds{} {}",
+                label, self.last_run_timestamp_seconds
+            ),
+            "# TYPE crunch_last_era_processed gauge".to_string(),
+            "# HELP crunch_last_era_processed Latest completed era scanned in the last run."
+                .to_string(),
+            format!(
+                "crunch_last_era_processed{} {}",
+                label, self.last_era_processed
+            ),
+            "# TYPE crunch_signer_balance_plancks gauge".to_string(),
+            "# HELP crunch_signer_balance_plancks Free balance of the signer account."
+                .to_string(),
+            format!(
+                "crunch_signer_balance_plancks{} {}",
+                label, self.signer_balance_plancks
+            ),
+            "# EOF".to_string(),
+        ];
+        format!("{}\n", lines.join("\n"))
+    }
+}
+
+/// Returns a copy of the current metrics
+pub fn snapshot() -> Metrics {
+    METRICS.lock().unwrap().clone()
+}
+
+pub fn set_network(network: &str) {
+    METRICS.lock().unwrap().network = network.to_string();
+}
+
+pub fn set_signer_balance(balance: u128) {
+    METRICS.lock().unwrap().signer_balance_plancks = balance;
+}
+
+pub fn set_active_era(era_index: EraIndex) {
+    METRICS.lock().unwrap().active_era = era_index;
+}
+
+/// Keeps the number of pages left unclaimed by each validator
+pub fn set_pending_unclaimed(validators: &[Validator]) {
+    METRICS.lock().unwrap().pending_unclaimed = validators
+        .iter()
+        .map(|v| {
+            let name = if v.name.is_empty() {
+                v.stash.to_string()
+            } else {
+                v.name.clone()
+            };
+            (name, v.unclaimed_pages())
+        })
+        .collect();
+}
+
+/// Keeps the error raised, discarding the oldest one once `MAX_RECENT_ERRORS` is reached
+pub fn add_error(error: &str) {
+    let mut metrics = METRICS.lock().unwrap();
+    if metrics.recent_errors.len() == MAX_RECENT_ERRORS {
+        metrics.recent_errors.pop_front();
+    }
+    metrics
+        .recent_errors
+        .push_back((now_seconds(), error.to_string()));
+}
+
+/// Adds the calls and fees of a payout summary to the respective counters
+pub fn add_payout_summary(era_index: EraIndex, summary: &PayoutSummary) {
+    let mut metrics = METRICS.lock().unwrap();
+    metrics.calls += summary.calls as u64;
+    metrics.calls_succeeded += summary.calls_succeeded as u64;
+    metrics.calls_failed += summary.calls_failed as u64;
+    metrics.fees_paid_plancks += summary.total_fees_paid;
+    metrics.last_calls_era = Some((era_index, summary.calls));
+    metrics.last_payout_summary = Some((era_index, summary.clone()));
+}
+
+/// Marks the run as completed at the current time
+pub fn set_last_run(era_index: EraIndex) {
+    let mut metrics = METRICS.lock().unwrap();
+    metrics.runs += 1;
+    metrics.last_era_processed = era_index;
+    metrics.last_run_timestamp_seconds = now_seconds();
+}
+
+/// Returns the current Unix time in seconds
+pub fn now_seconds() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Serves the metrics on `CRUNCH_METRICS_PORT` in a background thread
+pub fn spawn_metrics_server() {
+    let config = CONFIG.clone();
+    let addr = format!("0.0.0.0:{}", config.metrics_port);
+    let listener = match TcpListener::bind(&addr) {
+        Ok(listener) => listener,
+        Err(e) => {
+            warn!("Metrics server could not be started on {}: {}", addr, e);
+            return;
+        }
+    };
+    info!("Metrics available at http://{}/metrics", addr);
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    if let Err(e) = handle_connection(stream) {
+                        debug!("Metrics request failed: {}", e);
+                    }
+                }
+                Err(e) => debug!("Metrics connection failed: {}", e),
+            }
+        }
+    });
+}
+
+fn handle_connection(mut stream: TcpStream) -> std::io::Result<()> {
+    // NOTE: The request is not parsed, every path responds with the metrics
+    let mut buffer = [0; 1024];
+    let _ = stream.read(&mut buffer)?;
+    let body = snapshot().render();
+    let response = format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: application/openmetrics-text; version=1.0.0; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes())?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_renders_metrics_labeled_by_network() {
+        let mut metrics = Metrics::default();
+        metrics.network = "Polkadot".to_string();
+        metrics.calls = 8;
+        metrics.last_calls_era = Some((1500, 4));
+        metrics.last_era_processed = 1500;
+        metrics.signer_balance_plancks = 10_000_000_000;
+
+        let text = metrics.render();
+        assert!(text.contains(
+            "crunch_payout_calls_total{network=\"polkadot\"} 8 # {era=\"1500\"} 4\n"
+        ));
+        assert!(text.contains("crunch_last_era_processed{network=\"polkadot\"} 1500\n"));
+        assert!(text.contains(
+            "crunch_signer_balance_plancks{network=\"polkadot\"} 10000000000\n"
+        ));
+        assert!(text.ends_with("# EOF\n"));
+    }
+}
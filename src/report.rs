@@ -19,13 +19,21 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 use crate::{
-    config::{RunMode, CONFIG},
+    config::{ReportSort, RunMode, StashSource, CONFIG},
     crunch::OnetData,
+    state::RunRecord,
 };
+use chrono::{TimeZone, Utc};
+use chrono_tz::Tz;
 use log::{info, warn};
 use rand::Rng;
 use regex::Regex;
-use std::collections::HashSet;
+use std::{
+    cmp,
+    collections::{BTreeMap, HashSet},
+    convert::TryFrom,
+    fs,
+};
 use subxt::{ext::sp_core::H256, utils::AccountId32};
 
 pub type EraIndex = u32;
@@ -37,6 +45,8 @@ pub struct Points {
     pub era_avg: f64,
     pub ci99_9_interval: (f64, f64),
     pub outlier_limits: (f64, f64),
+    // NOTE: only available if raw points export is enabled
+    pub individual: Vec<(AccountId32, u32)>,
 }
 
 #[derive(Debug, Clone)]
@@ -48,6 +58,10 @@ pub struct Payout {
     pub nominators_amount_value: u128,
     pub nominators_quantity: u32,
     pub points: Points,
+    // NOTE: only available if rewards confirmation is enabled
+    pub credited_amount_value: Option<u128>,
+    // NOTE: only available if nominator detail export is enabled
+    pub nominators: Vec<(AccountId32, u128)>,
 }
 
 #[derive(Debug, Clone)]
@@ -56,6 +70,58 @@ pub struct Batch {
     pub extrinsic: H256,
 }
 
+#[derive(Debug, Clone)]
+pub struct FailedBatch {
+    pub block_number: u32,
+    pub extrinsic: H256,
+    pub calls: u32,
+    pub error: String,
+}
+
+impl FailedBatch {
+    /// Returns the failed batch of the calls not dispatched by a batch interrupted at
+    /// `index`, the failing call and every call after it, or None if the index is out of
+    /// the batch bounds
+    pub fn interrupted(
+        block_number: u32,
+        extrinsic: H256,
+        calls: usize,
+        index: u32,
+        error: String,
+    ) -> Option<FailedBatch> {
+        let index = usize::try_from(index).ok().filter(|index| *index < calls)?;
+        Some(FailedBatch {
+            block_number,
+            extrinsic,
+            calls: (calls - index) as u32,
+            error,
+        })
+    }
+}
+
+/// Number of pages, and of distinct eras, no longer kept in memory
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DroppedPages {
+    pub pages: usize,
+    pub eras: usize,
+}
+
+impl DroppedPages {
+    fn add(&mut self, pages: &[(EraIndex, PageIndex)]) {
+        self.pages += pages.len();
+        self.eras += count_eras(pages);
+    }
+}
+
+/// Returns the number of distinct eras of the pages
+fn count_eras(pages: &[(EraIndex, PageIndex)]) -> usize {
+    pages
+        .iter()
+        .map(|(era_index, _page_index)| *era_index)
+        .collect::<HashSet<EraIndex>>()
+        .len()
+}
+
 #[derive(Debug, Clone)]
 pub struct Validator {
     pub stash: AccountId32,
@@ -68,7 +134,15 @@ pub struct Validator {
     pub unclaimed: Vec<(EraIndex, PageIndex)>,
     pub payouts: Vec<Payout>,
     pub warnings: Vec<String>,
+    pub risks: Vec<String>,
     pub onet: Option<OnetData>,
+    // Commission in parts per billion (Perbill)
+    pub commission: Option<u32>,
+    pub reward_destination: Option<RewardDestination>,
+    // NOTE: only available if unclaimed eras are scanned in chunks, the pages of the
+    // chunks already processed are dropped and only counted
+    pub claimed_dropped: DroppedPages,
+    pub unclaimed_dropped: DroppedPages,
 }
 
 impl Validator {
@@ -84,13 +158,89 @@ impl Validator {
             unclaimed: Vec::new(),
             payouts: Vec::new(),
             warnings: Vec::new(),
+            risks: Vec::new(),
             onet: None,
+            commission: None,
+            reward_destination: None,
+            claimed_dropped: DroppedPages::default(),
+            unclaimed_dropped: DroppedPages::default(),
+        }
+    }
+
+    /// Drops the claimed and unclaimed pages kept, counting them instead.
+    /// NOTE: The pages dropped must be from eras not kept, so that eras are not counted twice
+    pub fn drop_pages(&mut self) {
+        self.claimed_dropped.add(&self.claimed);
+        self.unclaimed_dropped.add(&self.unclaimed);
+        self.claimed.clear();
+        self.unclaimed.clear();
+    }
+
+    /// Returns the number of claimed pages, including the ones dropped
+    pub fn claimed_pages(&self) -> usize {
+        self.claimed.len() + self.claimed_dropped.pages
+    }
+
+    /// Returns the number of unclaimed pages, including the ones dropped
+    pub fn unclaimed_pages(&self) -> usize {
+        self.unclaimed.len() + self.unclaimed_dropped.pages
+    }
+
+    /// Returns the number of eras with claimed pages, including the ones dropped
+    pub fn claimed_eras(&self) -> usize {
+        count_eras(&self.claimed) + self.claimed_dropped.eras
+    }
+
+    /// Returns the number of eras with unclaimed pages, including the ones dropped
+    pub fn unclaimed_eras(&self) -> usize {
+        count_eras(&self.unclaimed) + self.unclaimed_dropped.eras
+    }
+
+    /// Returns the amount of the payout expected to be credited as free balance.
+    /// NOTE: The validator reward is restaked if the destination is `Staked` and not paid
+    /// if the destination is `None`, so it is not expected as free balance
+    pub fn expected_credited_amount(&self, payout: &Payout) -> u128 {
+        match self.reward_destination {
+            Some(RewardDestination::Staked) | Some(RewardDestination::None) => {
+                payout.nominators_amount_value
+            }
+            _ => payout.validator_amount_value + payout.nominators_amount_value,
+        }
+    }
+
+    /// Returns a note if the validator commission is at or above the given percentage,
+    /// since its nominators receive little or no rewards
+    pub fn high_commission_note(&self, threshold: u32) -> Option<String> {
+        let commission = self.commission?;
+        if (commission as u64) < threshold as u64 * 10_000_000 {
+            return None;
+        }
+        Some(format!(
+            "ℹ️ Commission at {}%, nominators receive {} rewards",
+            commission as f64 / 10_000_000.0,
+            if commission >= 1_000_000_000 {
+                "no"
+            } else {
+                "little"
+            }
+        ))
+    }
+
+    /// Returns whether the validator was paid in this run, is up to date or still has
+    /// pages left unclaimed, so that a quiet report is not mistaken for inaction
+    pub fn payout_status(&self) -> PayoutStatus {
+        if !self.payouts.is_empty() {
+            PayoutStatus::Paid(self.payouts.len())
+        } else if self.unclaimed_pages() == 0 {
+            PayoutStatus::UpToDate
+        } else {
+            PayoutStatus::Pending(self.unclaimed_pages())
         }
     }
 
     pub fn display_parent_identity(&self) -> String {
         if self.has_identity {
-            self.parent_identity.clone()
+            format_identity(&self.parent_identity)
         } else {
             "⚠️ No Identity ⚠️".to_string()
         }
@@ -99,11 +249,67 @@ impl Validator {
 
 pub type Validators = Vec<Validator>;
 
+#[derive(Debug, Clone, PartialEq)]
+pub enum RewardDestination {
+    /// Rewards are added to the bonded amount
+    Staked,
+    /// Rewards are paid to the stash account as free balance
+    Stash,
+    /// Rewards are paid to the controller account as free balance
+    Controller,
+    /// Rewards are paid to the given account as free balance
+    Account(AccountId32),
+    /// Rewards are not paid
+    None,
+}
+
+impl std::fmt::Display for RewardDestination {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Staked => write!(f, "Staked (restaked, not credited as free balance)"),
+            Self::Stash => write!(f, "Stash"),
+            Self::Controller => write!(f, "Controller"),
+            Self::Account(account) => write!(f, "Account <code>{}</code>", account),
+            Self::None => write!(f, "None (rewards are not paid)"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PayoutStatus {
+    /// Number of payouts submitted by crunch in this run
+    Paid(usize),
+    /// No payouts submitted and nothing left to claim
+    UpToDate,
+    /// Number of pages left unclaimed with no payouts submitted in this run
+    Pending(usize),
+}
+
+impl std::fmt::Display for PayoutStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Paid(1) => {
+                write!(f, "✅ Paid by <code>crunch</code> in this run → 1 payout")
+            }
+            Self::Paid(n) => write!(
+                f,
+                "✅ Paid by <code>crunch</code> in this run → {} payouts",
+                n
+            ),
+            Self::UpToDate => write!(f, "☑️ Up to date → nothing left to claim"),
+            Self::Pending(1) => write!(f, "⏳ 1 page left unclaimed"),
+            Self::Pending(n) => write!(f, "⏳ {} pages left unclaimed", n),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct SignerDetails {
     pub account: AccountId32,
     pub name: String,
     pub warnings: Vec<String>,
+    // NOTE: only available if the signer was topped up from the funding account in this run
+    pub topup_amount: Option<u128>,
 }
 
 #[derive(Debug, Clone)]
@@ -112,6 +318,56 @@ pub struct Network {
     pub name: String,
     pub token_symbol: String,
     pub token_decimals: u8,
+    pub token_price: Option<f64>,
+}
+
+impl Network {
+    /// Returns the explorer link of the validator stash, from the template defined by
+    /// `CRUNCH_REPORT_VALIDATOR_URL_TEMPLATE`
+    pub fn validator_url(&self, stash: &AccountId32) -> String {
+        let config = CONFIG.clone();
+        format_explorer_url(
+            &config.report_validator_url_template,
+            &self.explorer_network(),
+            &stash.to_string(),
+            "",
+            "",
+        )
+    }
+
+    /// Returns the explorer link of the extrinsic, from the template defined by
+    /// `CRUNCH_REPORT_EXTRINSIC_URL_TEMPLATE`
+    pub fn extrinsic_url(&self, block_number: u32, extrinsic: &H256) -> String {
+        let config = CONFIG.clone();
+        format_explorer_url(
+            &config.report_extrinsic_url_template,
+            &self.explorer_network(),
+            "",
+            &block_number.to_string(),
+            &format!("{:?}", extrinsic),
+        )
+    }
+
+    /// Returns the network name as used in explorer subdomains (e.g. polkadot)
+    fn explorer_network(&self) -> String {
+        self.name.to_lowercase().trim().replace(" ", "")
+    }
+
+    /// Returns the fiat equivalent of the amount if the token price is available
+    pub fn fiat_amount(&self, value: u128) -> String {
+        let config = CONFIG.clone();
+        match self.token_price {
+            Some(price) => format!(
+                " (≈ {} {})",
+                format_number(
+                    value as f64 / 10f64.powi(self.token_decimals.into()) * price,
+                    2
+                ),
+                config.fiat_currency.to_uppercase()
+            ),
+            None => String::new(),
+        }
+    }
 }
 
 #[derive(Debug, Default, Clone)]
@@ -121,6 +377,34 @@ pub struct PayoutSummary {
     pub calls_failed: u32,
     pub next_minimum_expected: u32,
     pub total_validators: u32,
+    pub total_fees_paid: u128,
+    pub stashes_window: Option<StashesWindow>,
+    pub previous_era_expected: u32,
+    pub previous_era_claimed: u32,
+    pub calls_deferred: u32,
+    // NOTE: only available if the run continues on batch failures or if a batch
+    // is interrupted
+    pub batches_failed: Vec<FailedBatch>,
+    // Number of calls of each batch submitted, in submission order
+    pub batch_sizes: Vec<u32>,
+    pub grouped_by_identity: bool,
+    // NOTE: only available if the comparison with the previous run is enabled
+    pub previous_run: Option<RunRecord>,
+}
+
+impl PayoutSummary {
+    /// Record the failed batch, counting its calls as failed
+    pub fn add_batch_failed(&mut self, batch: FailedBatch) {
+        self.calls_failed += batch.calls;
+        self.batches_failed.push(batch);
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct StashesWindow {
+    pub start: usize,
+    pub size: usize,
+    pub total: usize,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -129,7 +413,44 @@ pub struct NominationPoolsSummary {
     pub calls_succeeded: u32,
     pub calls_failed: u32,
     pub total_members: u32,
+    // NOTE: members skipped because their rewards were claimed before submission
+    pub calls_no_rewards: u32,
+    // NOTE: members skipped because they were compounded within the minimum interval of eras
+    pub members_not_due: u32,
     pub batches: Vec<Batch>,
+    // NOTE: only available if a batch is interrupted
+    pub batches_failed: Vec<FailedBatch>,
+    // NOTE: rewards compounded per pool id
+    pub compounded: BTreeMap<u32, PoolCompounded>,
+}
+
+impl NominationPoolsSummary {
+    /// Record the failed batch, counting its calls as failed
+    pub fn add_batch_failed(&mut self, batch: FailedBatch) {
+        self.calls_failed += batch.calls;
+        self.batches_failed.push(batch);
+    }
+
+    /// Add the rewards bonded by a member back into the pool
+    pub fn add_compounded(&mut self, pool_id: u32, amount: u128) {
+        let pool = self.compounded.entry(pool_id).or_default();
+        pool.members += 1;
+        pool.amount += amount;
+    }
+
+    pub fn total_compounded(&self) -> u128 {
+        self.compounded.values().map(|pool| pool.amount).sum()
+    }
+
+    pub fn total_compounded_members(&self) -> u32 {
+        self.compounded.values().map(|pool| pool.members).sum()
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct PoolCompounded {
+    pub members: u32,
+    pub amount: u128,
 }
 
 #[derive(Debug)]
@@ -141,6 +462,15 @@ pub struct RawData {
     pub pools_summary: Option<NominationPoolsSummary>,
 }
 
+#[derive(Debug, Clone)]
+pub struct StashSummary {
+    pub stash: AccountId32,
+    pub name: String,
+    pub claimed: usize,
+    pub unclaimed: usize,
+    pub oldest_unclaimed_era: Option<EraIndex>,
+}
+
 type Body = Vec<String>;
 
 #[derive(Clone, PartialEq)]
@@ -177,36 +507,124 @@ impl Verbosity {
 
 pub struct Report {
     body: Body,
+    // NOTE: public body is a redacted version of the body without sensitive
+    // information (e.g. stash addresses, signer) to be sent to public rooms
+    public_body: Body,
+    // NOTE: template variables are only used if a custom report template is defined
+    template_vars: Vec<(String, String)>,
     verbosity: Verbosity,
+    // NOTE: boring style removes every emoji from the message sent (e.g. 🚨 ⚠️ 💙 🎶)
+    // so that only plain text remains. The report logged is kept as is
+    boring: bool,
 }
 
 impl Report {
     pub fn new() -> Report {
         Report {
             body: Vec::new(),
+            public_body: Vec::new(),
+            template_vars: Vec::new(),
             verbosity: Verbosity::from_config(),
+            boring: CONFIG.is_boring,
+        }
+    }
+
+    /// Returns a dedicated report containing only the nomination pools compound info
+    pub fn from_pools(
+        network: &Network,
+        pools_summary: NominationPoolsSummary,
+    ) -> Report {
+        let mut report = Report::new();
+        report.add_raw_text(format!(
+            "💙 <b>{}</b> is playing era <i>{}</i> 🎶 ",
+            network.name, network.active_era
+        ));
+        report.add_break();
+        add_pools_summary(&mut report, network, pools_summary);
+
+        // Log report
+        report.log();
+
+        report
+    }
+
+    /// Returns the body lines in the report style defined by the user
+    fn styled(&self, body: &Body) -> Body {
+        if self.boring {
+            return body.iter().map(|line| strip_emoji(line)).collect();
         }
+        body.clone()
+    }
+
+    pub fn add_template_var(&mut self, key: &str, value: String) {
+        self.template_vars.push((key.to_string(), value));
     }
 
     pub fn add_raw_text(&mut self, t: String) {
+        self.body.push(t.clone());
+        self.public_body.push(t);
+    }
+
+    /// Add text only to the private body
+    pub fn add_private_raw_text(&mut self, t: String) {
         self.body.push(t);
     }
 
+    /// Add text only to the public body
+    pub fn add_public_raw_text(&mut self, t: String) {
+        self.public_body.push(t);
+    }
+
     pub fn add_text(&mut self, t: String) {
         if self.verbosity.is_long() {
             self.add_raw_text(t);
         }
     }
+
+    pub fn add_private_text(&mut self, t: String) {
+        if self.verbosity.is_long() {
+            self.add_private_raw_text(t);
+        }
+    }
+
     pub fn add_break(&mut self) {
         self.add_raw_text("".into());
     }
 
+    /// Replace the whole body by a single line
+    pub fn collapse(&mut self, t: String) {
+        self.body = vec![t.clone()];
+        self.public_body = vec![t];
+    }
+
     pub fn message(&self) -> String {
-        self.body.join("\n")
+        self.styled(&self.body).join("\n")
     }
 
     pub fn formatted_message(&self) -> String {
-        self.body.join("<br>")
+        let config = CONFIG.clone();
+        if !config.report_template_path.is_empty() {
+            match fs::read_to_string(&config.report_template_path) {
+                Ok(template) => {
+                    let mut vars = self.template_vars.clone();
+                    vars.push(("body".to_string(), self.styled(&self.body).join("<br>")));
+                    return render_template(&template, &vars);
+                }
+                Err(e) => warn!(
+                    "Unable to read report template {} error: {:?}",
+                    config.report_template_path, e
+                ),
+            }
+        }
+        self.styled(&self.body).join("<br>")
+    }
+
+    pub fn public_message(&self) -> String {
+        self.styled(&self.public_body).join("\n")
+    }
+
+    pub fn public_formatted_message(&self) -> String {
+        self.styled(&self.public_body).join("<br>")
     }
 
     pub fn log(&self) {
@@ -220,12 +638,40 @@ impl Report {
 
 impl From<RawData> for Report {
     /// Converts a Crunch `RawData` into a [`Report`].
-    fn from(data: RawData) -> Report {
+    fn from(mut data: RawData) -> Report {
         let config = CONFIG.clone();
         let mut report = Report::new();
 
+        // Apply the identity display policy before anything is rendered
+        for v in data.validators.iter_mut() {
+            v.name = format_identity(&v.name);
+        }
+        data.signer_details.name = format_identity(&data.signer_details.name);
+
+        // Single success line used if nothing in the report needs attention
+        let minimal_summary = if config.report_minimal_on_success && is_uneventful(&data)
+        {
+            Some(format!(
+                "✅ <b>{}</b> era <i>{}</i> → {} payouts crunched, {} {} rewarded",
+                data.network.name,
+                data.network.active_era,
+                data.payout_summary.calls_succeeded,
+                format_balance(
+                    data.validators
+                        .iter()
+                        .flat_map(|v| v.payouts.iter())
+                        .map(|p| p.validator_amount_value + p.nominators_amount_value)
+                        .sum(),
+                    data.network.token_decimals
+                ),
+                data.network.token_symbol
+            ))
+        } else {
+            None
+        };
+
         let summary_crunch_desc = if data.payout_summary.calls_succeeded > 0 {
-            if config.group_identity_enabled {
+            if data.payout_summary.grouped_by_identity {
                 format!(
                     "{} crunched <b>{}</b> ({:.0}%) → ",
                     data.validators[0].display_parent_identity(),
@@ -249,7 +695,9 @@ impl From<RawData> for Report {
 
         let mut prefix = "Next".to_string();
 
-        if data.payout_summary.calls_succeeded == 0 && config.group_identity_enabled {
+        if data.payout_summary.calls_succeeded == 0
+            && data.payout_summary.grouped_by_identity
+        {
             prefix = format!("{} next", data.validators[0].display_parent_identity());
         }
 
@@ -267,6 +715,13 @@ impl From<RawData> for Report {
             format!("{} era expect <b>NO</b> rewards {}", prefix, Random::Grumpy)
         };
 
+        // Make it clear that nothing was submitted
+        if config.dry_run_enabled {
+            report.add_raw_text(
+                "🧪 <b>DRY RUN</b> → no payouts were signed or submitted".to_string(),
+            );
+        }
+
         report.add_raw_text(format!(
             "<details><summary>{}{}</summary>",
             summary_crunch_desc, summary_next_desc,
@@ -290,27 +745,202 @@ impl From<RawData> for Report {
             data.network.name, data.network.active_era
         ));
 
+        // Expected vs actual payouts of the previous era
+        if data.payout_summary.previous_era_claimed
+            < data.payout_summary.previous_era_expected
+        {
+            let warning = format!(
+                "{} of {} validators expected to be paid for era {} are still unclaimed",
+                data.payout_summary.previous_era_expected
+                    - data.payout_summary.previous_era_claimed,
+                data.payout_summary.previous_era_expected,
+                data.network.active_era - 1
+            );
+            report.add_raw_text(format!("⚠️ {} ⚠️", warning));
+            warn!("{}", warning);
+        }
+
+        // Changes since the previous run
+        if let Some(previous) = &data.payout_summary.previous_run {
+            let current = RunRecord::from_run(&data.validators, &data.payout_summary);
+            for change in compare_runs(previous, &current) {
+                report.add_raw_text(format!("📊 {}", change));
+            }
+        }
+
+        // Calls left for the next run once the maximum total calls is reached
+        if data.payout_summary.calls_deferred > 0 {
+            let note = format!(
+                "{} payout calls carried to the next run, maximum of {} calls per run reached",
+                data.payout_summary.calls_deferred, config.max_total_calls
+            );
+            report.add_raw_text(format!("⏭️ {}", note));
+            info!("{}", note);
+        }
+
+        // Shape of the batches submitted
+        if let Some(shape) = describe_batch_sizes(&data.payout_summary.batch_sizes) {
+            report.add_raw_text(format!("📦 {}", shape));
+        }
+
+        // Batches that failed without interrupting the run
+        for batch in &data.payout_summary.batches_failed {
+            report.add_private_raw_text(format!(
+                "❌ Batch of {} calls failed at block #{} (<a href=\"{}\">{}</a>) → {}",
+                batch.calls,
+                batch.block_number,
+                data.network
+                    .extrinsic_url(batch.block_number, &batch.extrinsic),
+                batch.extrinsic.to_string(),
+                batch.error
+            ));
+        }
+
+        // Recent eras excluded from the unclaimed scan
+        if let Some(skipped) =
+            describe_skipped_recent_eras(data.network.active_era, config.skip_recent_eras)
+        {
+            report.add_raw_text(format!("⏸️ {}", skipped));
+        }
+
+        // Stash sources, if restricted by the user
+        if ![StashSource::Config, StashSource::Remote, StashSource::Pools]
+            .iter()
+            .all(|source| config.stash_sources.contains(source))
+        {
+            report.add_raw_text(format!(
+                "🗂️ Stashes collected from <i>{}</i> only",
+                config
+                    .stash_sources
+                    .iter()
+                    .map(|source| source.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ));
+        }
+
+        // Stashes window processed in this run
+        if let Some(window) = &data.payout_summary.stashes_window {
+            report.add_raw_text(format!(
+                "🔄 Stashes <i>{}..{}</i> of {} processed in this run → {} remaining in rotation",
+                window.start + 1,
+                (window.start + window.size - 1) % window.total + 1,
+                window.total,
+                window.total - window.size
+            ));
+        }
+
         // Signer
-        report.add_text(format!(
+        report.add_private_text(format!(
             "<br>✍️ Signer &middot; <code>{}</code>",
             data.signer_details.name
         ));
+        if let Some(amount) = data.signer_details.topup_amount {
+            report.add_private_raw_text(format!(
+                "⛽ Signer topped up with {} {} from the funding account",
+                format_balance(amount, data.network.token_decimals),
+                data.network.token_symbol
+            ));
+        }
         for warning in data.signer_details.warnings {
-            report.add_raw_text(format!("⚠️ {} ⚠️", warning.clone()));
+            report.add_private_raw_text(format!("⚠️ {} ⚠️", warning.clone()));
             warn!("{}", warning);
         }
 
+        // Total amount rewarded by the payouts submitted in this run
+        let total_rewarded: u128 = data
+            .validators
+            .iter()
+            .flat_map(|v| v.payouts.iter())
+            .map(|p| p.validator_amount_value + p.nominators_amount_value)
+            .sum();
+
+        // Set template variables
+        report.add_template_var("network", data.network.name.clone());
+        report.add_template_var("era", data.network.active_era.to_string());
+        report.add_template_var("token_symbol", data.network.token_symbol.clone());
+        report.add_template_var(
+            "validators",
+            data.validators
+                .iter()
+                .map(|v| {
+                    format!(
+                        "<b>{}</b> → {} payouts {} {}",
+                        v.name,
+                        v.payouts.len(),
+                        format_balance(
+                            v.payouts
+                                .iter()
+                                .map(|p| p.validator_amount_value
+                                    + p.nominators_amount_value)
+                                .sum::<u128>(),
+                            data.network.token_decimals
+                        ),
+                        data.network.token_symbol
+                    )
+                })
+                .collect::<Vec<String>>()
+                .join("<br>"),
+        );
+        report.add_template_var("calls", data.payout_summary.calls.to_string());
+        report.add_template_var(
+            "calls_succeeded",
+            data.payout_summary.calls_succeeded.to_string(),
+        );
+        report.add_template_var(
+            "calls_failed",
+            data.payout_summary.calls_failed.to_string(),
+        );
+        report.add_template_var(
+            "next_minimum_expected",
+            data.payout_summary.next_minimum_expected.to_string(),
+        );
+        report.add_template_var(
+            "total_validators",
+            data.payout_summary.total_validators.to_string(),
+        );
+        report.add_template_var(
+            "total_rewarded",
+            format_balance(total_rewarded, data.network.token_decimals),
+        );
+        report.add_template_var(
+            "total_fees_paid",
+            format_balance(
+                data.payout_summary.total_fees_paid,
+                data.network.token_decimals,
+            ),
+        );
+
         // Validators info
-        for validator in data.validators {
+        let mut validators = data.validators;
+        sort_validators(&mut validators, config.report_sort);
+        for validator in validators {
             report.add_break();
             let is_active_desc = if validator.is_active { "🟢" } else { "🔴" };
-            report.add_raw_text(format!(
-                "{} <b><a href=\"https://{}.subscan.io/validator/{}\">{}</a></b>",
+            report.add_private_raw_text(format!(
+                "{} <b><a href=\"{}\">{}</a></b>",
                 is_active_desc,
-                data.network.name.to_lowercase().trim().replace(" ", ""),
-                validator.stash,
+                data.network.validator_url(&validator.stash),
                 validator.name,
             ));
+            report.add_public_raw_text(format!(
+                "{} <b>{}</b>",
+                is_active_desc, validator.name,
+            ));
+            // Show controller account if enabled by the user
+            if config.report_include_controller {
+                if let Some(controller) = &validator.controller {
+                    report.add_private_raw_text(format!(
+                        "🎮 Controller &middot; <code>{}</code>",
+                        controller
+                    ));
+                }
+            }
+            // Show validator risks (chilled or slashed)
+            for risk in validator.risks.iter() {
+                report.add_raw_text(format!("🚨 {} 🚨", risk));
+                warn!("{} * {}", validator.stash, risk);
+            }
             // Show validator warnings
             if validator.warnings.len() > 0 {
                 for warning in validator.warnings {
@@ -320,11 +950,29 @@ impl From<RawData> for Report {
                 continue;
             }
 
-            report.add_text(format!(
+            report.add_private_text(format!(
                 "💰 Stash &middot; <code>{}</code>",
                 validator.stash
             ));
 
+            // Show where the validator rewards land
+            if let Some(destination) = &validator.reward_destination {
+                report.add_private_text(format!(
+                    "🎯 Reward destination &middot; {}",
+                    destination
+                ));
+            }
+
+            // Show whether crunch paid the validator or there was nothing to claim
+            report.add_raw_text(validator.payout_status().to_string());
+
+            // Show an informational note for high commission validators
+            if let Some(note) =
+                validator.high_commission_note(config.high_commission_warn)
+            {
+                report.add_raw_text(note);
+            }
+
             // Check if there are no payouts
             if validator.payouts.len() == 0 {
                 if validator.is_active {
@@ -341,14 +989,20 @@ impl From<RawData> for Report {
                 }
             } else {
                 // Show Validator payout info
-                for payout in validator.payouts {
+                for payout in &validator.payouts {
                     // Points
                     let reward_amount = format!(
-                        "{:.4} {} {}",
-                        (payout.validator_amount_value + payout.nominators_amount_value)
-                            as f64
-                            / 10f64.powi(data.network.token_decimals.into()),
+                        "{} {}{} {}",
+                        format_balance(
+                            payout.validator_amount_value
+                                + payout.nominators_amount_value,
+                            data.network.token_decimals
+                        ),
                         data.network.token_symbol,
+                        data.network.fiat_amount(
+                            payout.validator_amount_value
+                                + payout.nominators_amount_value
+                        ),
                         good_performance(
                             payout.points.validator.into(),
                             payout.points.ci99_9_interval.1,
@@ -358,10 +1012,13 @@ impl From<RawData> for Report {
 
                     // Validator reward amount
                     let stash_amount = format!(
-                        "{:.4} {}",
-                        payout.validator_amount_value as f64
-                            / 10f64.powi(data.network.token_decimals.into()),
-                        data.network.token_symbol
+                        "{} {}{}",
+                        format_balance(
+                            payout.validator_amount_value,
+                            data.network.token_decimals
+                        ),
+                        data.network.token_symbol,
+                        data.network.fiat_amount(payout.validator_amount_value)
                     );
                     let stash_amount_percentage = (payout.validator_amount_value as f64
                         / (payout.validator_amount_value + payout.nominators_amount_value)
@@ -396,10 +1053,13 @@ impl From<RawData> for Report {
 
                     // Nominators reward amount
                     let nominators_amount = format!(
-                        "{:.4} {}",
-                        payout.nominators_amount_value as f64
-                            / 10f64.powi(data.network.token_decimals.into()),
-                        data.network.token_symbol
+                        "{} {}{}",
+                        format_balance(
+                            payout.nominators_amount_value,
+                            data.network.token_decimals
+                        ),
+                        data.network.token_symbol,
+                        data.network.fiat_amount(payout.nominators_amount_value)
                     );
                     let nominators_amount_percentage = (payout.nominators_amount_value
                         as f64
@@ -414,24 +1074,48 @@ impl From<RawData> for Report {
                     ));
 
                     // Block number
-                    report.add_raw_text(format!(
+                    report.add_private_raw_text(format!(
                         "💯 Payout for era <del>{}</del> finalized at block #{}
-                        (<a href=\"https://{}.subscan.io/extrinsic/{:?}\">{}</a>) ✨",
+                        (<a href=\"{}\">{}</a>) ✨",
                         payout.era_index,
                         payout.block_number,
-                        data.network.name.to_lowercase().trim().replace(" ", ""),
-                        payout.extrinsic,
+                        data.network
+                            .extrinsic_url(payout.block_number, &payout.extrinsic),
                         payout.extrinsic.to_string()
                     ));
+
+                    // Rewards not fully credited to the reward destinations
+                    if let Some(credited_amount_value) = payout.credited_amount_value {
+                        let rewarded_amount_value =
+                            validator.expected_credited_amount(payout);
+                        if credited_amount_value < rewarded_amount_value {
+                            let warning = format!(
+                                "Only {} of {} {} rewarded in era {} were credited",
+                                format_balance(
+                                    credited_amount_value,
+                                    data.network.token_decimals
+                                ),
+                                format_balance(
+                                    rewarded_amount_value,
+                                    data.network.token_decimals
+                                ),
+                                data.network.token_symbol,
+                                payout.era_index
+                            );
+                            report.add_raw_text(format!("⚠️ {} ⚠️", warning));
+                            warn!("{} * {}", validator.stash, warning);
+                        }
+                    }
                 }
 
                 // Check if there are still eras left to claim
-                if validator.unclaimed.len() > 0 {
-                    let symbols = number_to_symbols(validator.unclaimed.len(), "⚡", 84);
+                if validator.unclaimed_pages() > 0 {
+                    let symbols =
+                        number_to_symbols(validator.unclaimed_pages(), "⚡", 84);
                     report.add_text(format!(
 						"{} There are still {} eras left with {} to <code>crunch</code> {}",
 						symbols,
-						validator.unclaimed.len(),
+						validator.unclaimed_pages(),
 						context(),
 						symbols
 					));
@@ -447,21 +1131,9 @@ impl From<RawData> for Report {
             // General stats
 
             // Inclusion
-            let eras_claimed = validator
-                .claimed
-                .clone()
-                .into_iter()
-                .map(|(era_index, _page_index)| era_index)
-                .collect::<HashSet<EraIndex>>()
-                .len();
-
-            let eras_unclaimed = validator
-                .unclaimed
-                .clone()
-                .into_iter()
-                .map(|(era_index, _page_index)| era_index)
-                .collect::<HashSet<EraIndex>>()
-                .len();
+            let eras_claimed = validator.claimed_eras();
+
+            let eras_unclaimed = validator.unclaimed_eras();
 
             let inclusion_percentage =
                 ((eras_claimed + eras_unclaimed) as f32 / 84.0) * 100.0;
@@ -474,14 +1146,14 @@ impl From<RawData> for Report {
             ));
 
             // Claimed
-            if validator.claimed.len() > 0 {
-                let claimed_percentage = (validator.claimed.len() as f32
-                    / (validator.claimed.len() + validator.unclaimed.len()) as f32)
+            if validator.claimed_pages() > 0 {
+                let claimed_percentage = (validator.claimed_pages() as f32
+                    / (validator.claimed_pages() + validator.unclaimed_pages()) as f32)
                     * 100.0;
                 report.add_text(format!(
                     "😋 Crunched {}/{} ({:.2}%)",
-                    validator.claimed.len(),
-                    validator.claimed.len() + validator.unclaimed.len(),
+                    validator.claimed_pages(),
+                    validator.claimed_pages() + validator.unclaimed_pages(),
                     claimed_percentage
                 ));
             }
@@ -500,72 +1172,41 @@ impl From<RawData> for Report {
             }
         }
 
+        // Fees paid by the signer account
+        if data.payout_summary.total_fees_paid > 0 {
+            report.add_break();
+            report.add_private_raw_text(format!(
+                "🧾 Crunched {} {} → spent {} {} in fees",
+                format_balance(total_rewarded, data.network.token_decimals),
+                data.network.token_symbol,
+                format_balance(
+                    data.payout_summary.total_fees_paid,
+                    data.network.token_decimals
+                ),
+                data.network.token_symbol,
+            ));
+        }
+
         report.add_break();
 
-        // Nomination Pools compound info
+        // Nomination Pools compound info, unless sent as its own message
         if (config.pool_members_compound_enabled
             || config.pool_only_operator_compound_enabled)
-            && data.pools_summary.is_some()
+            && !config.report_split_pools
         {
-            let pool_summary_data = data.pools_summary.unwrap();
-
-            let threshold = format!(
-                "{:.4} {}",
-                config.pool_compound_threshold as f64
-                    / 10f64.powi(data.network.token_decimals.into()),
-                data.network.token_symbol,
-            );
-
-            let pools_desc = if config.pool_ids.len() == 1 {
-                format!("Pool {}", config.pool_ids.get(0).unwrap())
-            } else {
-                format!("Pools {:?}", config.pool_ids)
-            };
-
-            if pool_summary_data.total_members > 0 {
-                let members_desc = if pool_summary_data.total_members == 1 {
-                    format!("1 reward")
-                } else {
-                    format!("{} rewards", pool_summary_data.total_members)
-                };
-
-                if config.pool_only_operator_compound_enabled {
-                    report.add_raw_text(format!(
-                        "♻️ Pool operator reward compounded from {}",
-                        pools_desc
-                    ));
-                } else {
-                    report.add_raw_text(format!(
-                        "♻️ {} compounded from {}",
-                        members_desc, pools_desc
-                    ));
-                }
-
-                for batch in pool_summary_data.batches {
-                    report.add_raw_text(format!(
-                        "💯 Batch finalized at block #{}
-                    (<a href=\"https://{}.subscan.io/extrinsic/{:?}\">{}</a>) ✨",
-                        batch.block_number,
-                        data.network.name.to_lowercase().trim().replace(" ", ""),
-                        batch.extrinsic,
-                        batch.extrinsic.to_string()
-                    ));
-                }
-            } else {
-                // NOTE: Just log if there are no pending rewards to compound
-                info!(
-                    "♻️ No pending rewards to compound above {} from {}",
-                    threshold, pools_desc,
-                );
+            if let Some(pools_summary) = data.pools_summary {
+                add_pools_summary(&mut report, &data.network, pools_summary);
+                report.add_break();
             }
-            report.add_break();
         }
 
         match config.run_mode {
             RunMode::Daily | RunMode::Turbo => {
+                let next_run = Utc::now().timestamp() + config.interval as i64;
                 report.add_raw_text(format!(
-                    "💤 The next <code>crunch</code> time will be in {} hours ⏱️",
-                    config.interval / 3600
+                    "💤 The next <code>crunch</code> time will be in {} hours ({}) ⏱️",
+                    config.interval / 3600,
+                    format_timestamp(next_run)
                 ));
             }
             RunMode::Era => {
@@ -584,10 +1225,215 @@ impl From<RawData> for Report {
         // Log report
         report.log();
 
+        // NOTE: The full report is always logged, only the message sent is collapsed
+        if let Some(summary) = minimal_summary {
+            if !config.dry_run_enabled {
+                report.collapse(summary);
+            }
+        }
+
         report
     }
 }
 
+/// Adds the nomination pools compound info to the report
+fn add_pools_summary(
+    report: &mut Report,
+    network: &Network,
+    pool_summary_data: NominationPoolsSummary,
+) {
+    let config = CONFIG.clone();
+
+    let threshold = format!(
+        "{} {}",
+        format_balance(
+            config.pool_compound_threshold.into(),
+            network.token_decimals
+        ),
+        network.token_symbol,
+    );
+
+    let pools_desc = if config.pool_ids.len() == 1 {
+        format!("Pool {}", config.pool_ids.get(0).unwrap())
+    } else {
+        format!("Pools {:?}", config.pool_ids)
+    };
+
+    if pool_summary_data.total_members > 0 {
+        let members_desc = if pool_summary_data.total_members == 1 {
+            format!("1 reward")
+        } else {
+            format!("{} rewards", pool_summary_data.total_members)
+        };
+
+        if config.pool_only_operator_compound_enabled {
+            report.add_raw_text(format!(
+                "♻️ Pool operator reward compounded from {}",
+                pools_desc
+            ));
+        } else {
+            report.add_raw_text(format!(
+                "♻️ {} compounded from {}",
+                members_desc, pools_desc
+            ));
+        }
+
+        if pool_summary_data.compounded.len() > 0 {
+            let compounded_members = pool_summary_data.total_compounded_members();
+            report.add_raw_text(format!(
+                "💰 Compounded <b>{} {}</b> across {} {}",
+                format_balance(
+                    pool_summary_data.total_compounded(),
+                    network.token_decimals
+                ),
+                network.token_symbol,
+                compounded_members,
+                if compounded_members == 1 {
+                    "member"
+                } else {
+                    "members"
+                },
+            ));
+            // NOTE: Breakdown per pool only if more than one pool was compounded
+            if pool_summary_data.compounded.len() > 1 {
+                for (pool_id, pool) in &pool_summary_data.compounded {
+                    report.add_raw_text(format!(
+                        "Pool {} &middot; {} {} ({} {})",
+                        pool_id,
+                        format_balance(pool.amount, network.token_decimals),
+                        network.token_symbol,
+                        pool.members,
+                        if pool.members == 1 {
+                            "member"
+                        } else {
+                            "members"
+                        },
+                    ));
+                }
+            }
+        }
+
+        if pool_summary_data.calls_no_rewards > 0 {
+            report.add_raw_text(format!(
+                "⏭️ {} skipped with no rewards left to compound",
+                if pool_summary_data.calls_no_rewards == 1 {
+                    "1 member".to_string()
+                } else {
+                    format!("{} members", pool_summary_data.calls_no_rewards)
+                }
+            ));
+        }
+
+        if pool_summary_data.members_not_due > 0 {
+            report.add_raw_text(format!(
+                "⏳ {} skipped, compounded less than {} eras ago",
+                if pool_summary_data.members_not_due == 1 {
+                    "1 member".to_string()
+                } else {
+                    format!("{} members", pool_summary_data.members_not_due)
+                },
+                config.pool_compound_min_interval_eras
+            ));
+        }
+
+        for batch in pool_summary_data.batches {
+            report.add_private_raw_text(format!(
+                "💯 Batch finalized at block #{}
+                    (<a href=\"{}\">{}</a>) ✨",
+                batch.block_number,
+                network.extrinsic_url(batch.block_number, &batch.extrinsic),
+                batch.extrinsic.to_string()
+            ));
+        }
+
+        // Batches interrupted before all the calls were dispatched
+        for batch in pool_summary_data.batches_failed {
+            report.add_private_raw_text(format!(
+                "❌ Batch of {} calls failed at block #{} (<a href=\"{}\">{}</a>) → {}",
+                batch.calls,
+                batch.block_number,
+                network.extrinsic_url(batch.block_number, &batch.extrinsic),
+                batch.extrinsic.to_string(),
+                batch.error
+            ));
+        }
+    } else {
+        // NOTE: Just log if there are no pending rewards to compound
+        info!(
+            "♻️ No pending rewards to compound above {} from {}",
+            threshold, pools_desc,
+        );
+    }
+}
+
+/// Describes the most recent eras excluded from the unclaimed scan (e.g. "Eras 98..99 skipped ...")
+fn describe_skipped_recent_eras(active_era: EraIndex, skip: u32) -> Option<String> {
+    let end = active_era.checked_sub(1)?;
+    if skip == 0 {
+        return None;
+    }
+    let start = active_era.saturating_sub(skip);
+    if start == end {
+        return Some(format!(
+            "Era {} skipped from the unclaimed scan, to be claimed in a later run",
+            end
+        ));
+    }
+    Some(format!(
+        "Eras {}..{} skipped from the unclaimed scan, to be claimed in a later run",
+        start, end
+    ))
+}
+
+/// Describes the number of batches submitted and the calls of each one (e.g. "Submitted 3 batches: 8/8/3 calls")
+fn describe_batch_sizes(sizes: &[u32]) -> Option<String> {
+    if sizes.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "Submitted {} batch{}: {} calls",
+        sizes.len(),
+        if sizes.len() > 1 { "es" } else { "" },
+        sizes
+            .iter()
+            .map(|size| size.to_string())
+            .collect::<Vec<String>>()
+            .join("/")
+    ))
+}
+
+/// Replaces the {network}, {stash}, {block} and {extrinsic} placeholders of the explorer URL template
+fn format_explorer_url(
+    template: &str,
+    network: &str,
+    stash: &str,
+    block: &str,
+    extrinsic: &str,
+) -> String {
+    template
+        .replace("{network}", network)
+        .replace("{stash}", stash)
+        .replace("{block}", block)
+        .replace("{extrinsic}", extrinsic)
+}
+
+/// Returns true if all payouts succeeded and there are no warnings to report
+fn is_uneventful(data: &RawData) -> bool {
+    data.payout_summary.calls_failed == 0
+        && data.payout_summary.batches_failed.is_empty()
+        && data.payout_summary.previous_era_claimed
+            >= data.payout_summary.previous_era_expected
+        && data.signer_details.warnings.is_empty()
+        && data
+            .validators
+            .iter()
+            .all(|v| v.warnings.is_empty() && v.risks.is_empty())
+        && data
+            .pools_summary
+            .as_ref()
+            .map_or(true, |pools| pools.calls_failed == 0)
+}
+
 pub fn replace_emoji_lowercase(string: &String) -> String {
     let regex = Regex::new(concat!(
         "[",
@@ -619,6 +1465,251 @@ pub fn replace_emoji_lowercase(string: &String) -> String {
     regex.replace_all(string, "").to_string().to_lowercase()
 }
 
+/// Applies the identity display policy defined by the user, stripping emoji
+/// and truncating identities longer than `CRUNCH_IDENTITY_MAX_LEN` characters
+pub fn format_identity(name: &str) -> String {
+    let config = CONFIG.clone();
+    let name = if config.strip_emoji {
+        strip_emoji(name)
+    } else {
+        name.to_string()
+    };
+    truncate_identity(&name, config.identity_max_len)
+}
+
+fn strip_emoji(string: &str) -> String {
+    // NOTE: Unlike `replace_emoji_lowercase` spaces and non latin scripts are kept
+    let regex = Regex::new(concat!(
+        "[",
+        "\u{01F000}-\u{01FAFF}",
+        "\u{002600}-\u{0027BF}",
+        "\u{002B00}-\u{002BFF}",
+        "\u{00231A}-\u{00231B}",
+        "\u{0023E9}-\u{0023FA}",
+        "\u{200d}",
+        "\u{fe0f}",
+        "\u{20e3}",
+        "\u{3030}",
+        "]+",
+    ))
+    .unwrap();
+
+    regex
+        .replace_all(string, "")
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+fn truncate_identity(name: &str, max_len: usize) -> String {
+    if max_len == 0 || name.chars().count() <= max_len {
+        return name.to_string();
+    }
+    format!(
+        "{}…",
+        name.chars().take(max_len).collect::<String>().trim_end()
+    )
+}
+
+/// Replaces every `{{key}}` placeholder in the template by the respective value
+fn render_template(template: &str, vars: &Vec<(String, String)>) -> String {
+    vars.iter()
+        .fold(template.trim().to_string(), |message, (key, value)| {
+            message.replace(&format!("{{{{{}}}}}", key), value)
+        })
+}
+
+/// Formats a number with the decimal and thousands separators of the locale defined by `CRUNCH_LOCALE`
+pub fn format_number(value: f64, precision: usize) -> String {
+    let config = CONFIG.clone();
+    format_number_with_locale(value, precision, &config.locale)
+}
+
+/// Formats a unix timestamp (in seconds) in the time zone defined by `CRUNCH_TIMEZONE`
+pub fn format_timestamp(timestamp: i64) -> String {
+    let config = CONFIG.clone();
+    format_timestamp_with_timezone(timestamp, &config.timezone)
+}
+
+/// note: an invalid time zone falls back to UTC
+fn format_timestamp_with_timezone(timestamp: i64, timezone: &str) -> String {
+    let tz: Tz = timezone.parse().unwrap_or(Tz::UTC);
+    match Utc.timestamp_opt(timestamp, 0).single() {
+        Some(datetime) => datetime
+            .with_timezone(&tz)
+            .format("%Y-%m-%d %H:%M %Z")
+            .to_string(),
+        None => timestamp.to_string(),
+    }
+}
+
+/// Formats an amount in plancks as an amount of tokens with 4 decimal places
+pub fn format_balance(value: u128, token_decimals: u8) -> String {
+    format_number(value as f64 / 10f64.powi(token_decimals.into()), 4)
+}
+
+/// Returns the thousands and decimal separators used by the given locale
+/// note: an empty locale means a neutral format with no thousands separator (e.g. 1234.56)
+fn locale_separators(locale: &str) -> (&'static str, &'static str) {
+    let language = locale
+        .split(|c| c == '_' || c == '-')
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    match language.as_str() {
+        "" => ("", "."),
+        "de" | "es" | "it" | "pt" | "nl" | "da" | "id" | "tr" => (".", ","),
+        "fr" | "pl" | "cs" | "sv" | "no" | "nb" | "fi" | "ru" | "uk" => (" ", ","),
+        _ => (",", "."),
+    }
+}
+
+fn format_number_with_locale(value: f64, precision: usize, locale: &str) -> String {
+    let (thousands_separator, decimal_separator) = locale_separators(locale);
+    let formatted = format!("{:.*}", precision, value.abs());
+    let (integer, fraction) = match formatted.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (formatted.as_str(), None),
+    };
+    let mut grouped = String::new();
+    for (i, c) in integer.chars().enumerate() {
+        if i > 0 && (integer.len() - i) % 3 == 0 {
+            grouped.push_str(thousands_separator);
+        }
+        grouped.push(c);
+    }
+    let sign = if value < 0.0 { "-" } else { "" };
+    match fraction {
+        Some(fraction) => format!("{}{}{}{}", sign, grouped, decimal_separator, fraction),
+        None => format!("{}{}", sign, grouped),
+    }
+}
+
+/// Sorts validators as defined by `CRUNCH_REPORT_SORT`
+/// note: validators are already sorted by name when collected, and since the sort is stable
+/// validators with the same reward or unclaimed eras keep that order
+pub fn sort_validators(validators: &mut Validators, report_sort: ReportSort) {
+    match report_sort {
+        ReportSort::Name => (),
+        ReportSort::Reward => validators.sort_by_key(|v| {
+            cmp::Reverse(
+                v.payouts
+                    .iter()
+                    .map(|p| p.validator_amount_value + p.nominators_amount_value)
+                    .sum::<u128>(),
+            )
+        }),
+        ReportSort::Unclaimed => {
+            validators.sort_by_key(|v| cmp::Reverse(v.unclaimed_pages()))
+        }
+    }
+}
+
+/// Returns the changes of the current run compared with the previous one
+pub fn compare_runs(previous: &RunRecord, current: &RunRecord) -> Vec<String> {
+    let delta = |current: u32, previous: u32| {
+        let delta = current as i64 - previous as i64;
+        if delta == 0 {
+            "same as".to_string()
+        } else {
+            format!("{:+}", delta)
+        }
+    };
+    let mut changes = vec![format!(
+        "<b>{}</b> calls succeeded ({} last run) &middot; <b>{}</b> validators paid ({} last run)",
+        current.calls_succeeded,
+        delta(current.calls_succeeded, previous.calls_succeeded),
+        current.validators_paid,
+        delta(current.validators_paid, previous.validators_paid),
+    )];
+    for warning in &current.warnings {
+        if !previous.warnings.contains(warning) {
+            changes.push(format!("New warning since last run: {}", warning));
+        }
+    }
+    for name in &current.behind {
+        if !previous.behind.contains(name) {
+            changes.push(format!("{} fell behind since last run", name));
+        }
+    }
+    changes
+}
+
+/// Returns the number of validators with rewards to be claimed, or already claimed, in the given era
+pub fn count_validators_with_rewards_in_era(
+    validators: &Validators,
+    era_index: EraIndex,
+) -> u32 {
+    validators
+        .iter()
+        .filter(|v| {
+            v.claimed
+                .iter()
+                .chain(v.unclaimed.iter())
+                .any(|(era, _)| *era == era_index)
+        })
+        .count() as u32
+}
+
+/// Returns the number of validators with all the rewards of the given era claimed
+pub fn count_validators_claimed_in_era(
+    validators: &Validators,
+    era_index: EraIndex,
+) -> u32 {
+    validators
+        .iter()
+        .filter(|v| {
+            (v.claimed.iter().any(|(era, _)| *era == era_index)
+                || v.payouts.iter().any(|p| p.era_index == era_index))
+                && !v.unclaimed.iter().any(|(era, _)| *era == era_index)
+        })
+        .count() as u32
+}
+
+/// Formats the per stash rollup displayed at the end of `crunch view` as a compact table
+pub fn stash_summary_table(summaries: &Vec<StashSummary>) -> Vec<String> {
+    let header = [
+        "Identity",
+        "Stash",
+        "Claimed",
+        "Unclaimed",
+        "Oldest unclaimed",
+    ];
+    let mut rows: Vec<[String; 5]> = vec![header.map(|h| h.to_string())];
+    for s in summaries {
+        rows.push([
+            s.name.clone(),
+            s.stash.to_string(),
+            s.claimed.to_string(),
+            s.unclaimed.to_string(),
+            match s.oldest_unclaimed_era {
+                Some(era_index) => era_index.to_string(),
+                None => "-".to_string(),
+            },
+        ]);
+    }
+
+    // Set each column width to the widest value in the column
+    let mut widths = [0; 5];
+    for row in &rows {
+        for (i, value) in row.iter().enumerate() {
+            widths[i] = cmp::max(widths[i], value.chars().count());
+        }
+    }
+
+    rows.iter()
+        .map(|row| {
+            row.iter()
+                .enumerate()
+                .map(|(i, value)| format!("{:<width$}", value, width = widths[i]))
+                .collect::<Vec<String>>()
+                .join(" | ")
+                .trim_end()
+                .to_string()
+        })
+        .collect()
+}
+
 fn number_to_symbols(n: usize, symbol: &str, max: usize) -> String {
     let cap: usize = match n {
         n if n < (max / 4) as usize => 1,
@@ -775,4 +1866,478 @@ mod tests {
         assert_eq!(good_performance(2620, ci99_9.1, iqr_interval.1), "😊 🔥");
         assert_eq!(good_performance(3160, ci99_9.1, iqr_interval.1), "🤑 🤯 🚀");
     }
+
+    #[test]
+    fn it_sums_pool_compounded_rewards() {
+        let mut summary = NominationPoolsSummary::default();
+        assert_eq!(summary.total_compounded(), 0);
+        summary.add_compounded(1, 100);
+        summary.add_compounded(1, 50);
+        summary.add_compounded(2, 25);
+        assert_eq!(summary.total_compounded(), 175);
+        assert_eq!(summary.total_compounded_members(), 3);
+        assert_eq!(summary.compounded.get(&1).unwrap().members, 2);
+        assert_eq!(summary.compounded.get(&2).unwrap().amount, 25);
+    }
+
+    #[test]
+    fn it_compares_runs() {
+        let previous = RunRecord {
+            calls_succeeded: 4,
+            validators_paid: 2,
+            warnings: vec!["Alice Slash recorded in era 7".to_string()],
+            behind: vec!["Alice".to_string()],
+            ..Default::default()
+        };
+        let current = RunRecord {
+            calls_succeeded: 7,
+            validators_paid: 2,
+            warnings: vec![
+                "Alice Slash recorded in era 7".to_string(),
+                "Bob Slash recorded in era 8".to_string(),
+            ],
+            behind: vec!["Alice".to_string(), "Bob".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            compare_runs(&previous, &current),
+            vec![
+                "<b>7</b> calls succeeded (+3 last run) &middot; <b>2</b> validators paid (same as last run)".to_string(),
+                "New warning since last run: Bob Slash recorded in era 8".to_string(),
+                "Bob fell behind since last run".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_renders_report_template() {
+        let vars = vec![
+            ("network".to_string(), "Polkadot".to_string()),
+            ("era".to_string(), "1500".to_string()),
+        ];
+        assert_eq!(
+            render_template("<b>{{network}}</b> era {{era}} {{unknown}}\n", &vars),
+            "<b>Polkadot</b> era 1500 {{unknown}}"
+        );
+    }
+
+    #[test]
+    fn it_formats_stash_summary_table() {
+        let summaries = vec![
+            StashSummary {
+                stash: AccountId32::from([0u8; 32]),
+                name: "TURBOFLAKES/01".to_string(),
+                claimed: 80,
+                unclaimed: 4,
+                oldest_unclaimed_era: Some(1200),
+            },
+            StashSummary {
+                stash: AccountId32::from([1u8; 32]),
+                name: "ALICE".to_string(),
+                claimed: 84,
+                unclaimed: 0,
+                oldest_unclaimed_era: None,
+            },
+        ];
+        let table = stash_summary_table(&summaries);
+        assert_eq!(table.len(), 3);
+        assert!(table[0].starts_with("Identity       | Stash"));
+        assert!(table[0].ends_with("| Claimed | Unclaimed | Oldest unclaimed"));
+        assert!(table[1].ends_with("| 80      | 4         | 1200"));
+        assert!(table[2].starts_with("ALICE          | "));
+        assert!(table[2].ends_with("| 84      | 0         | -"));
+    }
+
+    #[test]
+    fn it_formats_timestamps_with_timezone() {
+        assert_eq!(
+            format_timestamp_with_timezone(1700000000, "UTC"),
+            "2023-11-14 22:13 UTC"
+        );
+        assert_eq!(
+            format_timestamp_with_timezone(1700000000, "Asia/Tokyo"),
+            "2023-11-15 07:13 JST"
+        );
+        assert_eq!(
+            format_timestamp_with_timezone(1700000000, "Not/AZone"),
+            "2023-11-14 22:13 UTC"
+        );
+    }
+
+    #[test]
+    fn it_describes_skipped_recent_eras() {
+        assert_eq!(describe_skipped_recent_eras(100, 0), None);
+        assert_eq!(describe_skipped_recent_eras(0, 1), None);
+        assert_eq!(
+            describe_skipped_recent_eras(100, 1),
+            Some(
+                "Era 99 skipped from the unclaimed scan, to be claimed in a later run"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            describe_skipped_recent_eras(100, 2),
+            Some(
+                "Eras 98..99 skipped from the unclaimed scan, to be claimed in a later run"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn it_describes_batch_sizes() {
+        assert_eq!(describe_batch_sizes(&[]), None);
+        assert_eq!(
+            describe_batch_sizes(&[3]),
+            Some("Submitted 1 batch: 3 calls".to_string())
+        );
+        assert_eq!(
+            describe_batch_sizes(&[8, 8, 3]),
+            Some("Submitted 3 batches: 8/8/3 calls".to_string())
+        );
+    }
+
+    #[test]
+    fn it_formats_explorer_urls() {
+        assert_eq!(
+            format_explorer_url(
+                "https://{network}.subscan.io/extrinsic/{extrinsic}",
+                "polkadot",
+                "",
+                "100",
+                "0x01"
+            ),
+            "https://polkadot.subscan.io/extrinsic/0x01"
+        );
+        assert_eq!(
+            format_explorer_url(
+                "https://explorer.example.com/{network}/block/{block}#{extrinsic}",
+                "kusama",
+                "",
+                "100",
+                "0x01"
+            ),
+            "https://explorer.example.com/kusama/block/100#0x01"
+        );
+    }
+
+    #[test]
+    fn it_formats_numbers_with_locale() {
+        assert_eq!(format_number_with_locale(1234.5678, 2, ""), "1234.57");
+        assert_eq!(
+            format_number_with_locale(1234567.891, 2, "en_US"),
+            "1,234,567.89"
+        );
+        assert_eq!(format_number_with_locale(1234.5678, 2, "de"), "1.234,57");
+        assert_eq!(
+            format_number_with_locale(1234.5678, 4, "fr-FR"),
+            "1 234,5678"
+        );
+        assert_eq!(format_number_with_locale(123.0, 0, "en"), "123");
+        assert_eq!(format_number_with_locale(-1234.5, 1, "en"), "-1,234.5");
+    }
+
+    #[test]
+    fn it_sorts_validators_by_unclaimed_eras() {
+        let mut v1 = Validator::new(AccountId32::from([1; 32]));
+        v1.unclaimed.push((99, 0));
+        let mut v2 = Validator::new(AccountId32::from([2; 32]));
+        v2.unclaimed.push((99, 0));
+        v2.unclaimed.push((98, 0));
+        let v3 = Validator::new(AccountId32::from([3; 32]));
+        let mut validators = vec![v1, v2, v3];
+
+        sort_validators(&mut validators, ReportSort::Name);
+        assert_eq!(validators[0].stash, AccountId32::from([1; 32]));
+
+        sort_validators(&mut validators, ReportSort::Unclaimed);
+        assert_eq!(validators[0].stash, AccountId32::from([2; 32]));
+        assert_eq!(validators[1].stash, AccountId32::from([1; 32]));
+        assert_eq!(validators[2].stash, AccountId32::from([3; 32]));
+    }
+
+    #[test]
+    fn it_counts_the_calls_of_an_interrupted_batch_as_failed() {
+        let interrupted =
+            |index| FailedBatch::interrupted(10, H256::zero(), 5, index, "error".into());
+
+        // the failing call and every call after it are not dispatched
+        let mut payout_summary: PayoutSummary = Default::default();
+        payout_summary.add_batch_failed(interrupted(2).unwrap());
+        assert_eq!(payout_summary.calls_failed, 3);
+        assert_eq!(payout_summary.batches_failed.len(), 1);
+        assert_eq!(payout_summary.batches_failed[0].calls, 3);
+
+        let mut pools_summary: NominationPoolsSummary = Default::default();
+        pools_summary.add_batch_failed(interrupted(0).unwrap());
+        pools_summary.add_batch_failed(interrupted(4).unwrap());
+        assert_eq!(pools_summary.calls_failed, 6);
+        assert_eq!(pools_summary.batches_failed.len(), 2);
+
+        // an index out of the batch bounds is ignored
+        assert!(interrupted(5).is_none());
+        assert!(interrupted(u32::MAX).is_none());
+    }
+
+    #[test]
+    fn it_counts_dropped_pages() {
+        let mut v = Validator::new(AccountId32::from([1; 32]));
+        v.claimed = vec![(90, 0), (90, 1), (91, 0)];
+        v.unclaimed = vec![(92, 0)];
+        v.drop_pages();
+        assert!(v.claimed.is_empty() && v.unclaimed.is_empty());
+
+        v.claimed = vec![(95, 0)];
+        v.unclaimed = vec![(96, 0), (97, 0)];
+        assert_eq!(v.claimed_pages(), 4);
+        assert_eq!(v.claimed_eras(), 3);
+        assert_eq!(v.unclaimed_pages(), 3);
+        assert_eq!(v.unclaimed_eras(), 3);
+        assert_eq!(v.payout_status(), PayoutStatus::Pending(3));
+    }
+
+    #[test]
+    fn it_describes_validator_payout_status() {
+        let mut v = Validator::new(AccountId32::from([1; 32]));
+        v.claimed.push((99, 0));
+        assert_eq!(v.payout_status(), PayoutStatus::UpToDate);
+
+        v.unclaimed.push((98, 0));
+        v.unclaimed.push((97, 0));
+        assert_eq!(v.payout_status(), PayoutStatus::Pending(2));
+        assert_eq!(v.payout_status().to_string(), "⏳ 2 pages left unclaimed");
+
+        v.payouts.push(Payout {
+            block_number: 1,
+            extrinsic: H256::zero(),
+            era_index: 98,
+            validator_amount_value: 0,
+            nominators_amount_value: 0,
+            nominators_quantity: 0,
+            points: Points::default(),
+            credited_amount_value: None,
+            nominators: Vec::new(),
+        });
+        assert_eq!(v.payout_status(), PayoutStatus::Paid(1));
+    }
+
+    #[test]
+    fn it_expects_staked_rewards_not_credited() {
+        let mut v = Validator::new(AccountId32::from([1; 32]));
+        let payout = Payout {
+            block_number: 1,
+            extrinsic: H256::zero(),
+            era_index: 98,
+            validator_amount_value: 10,
+            nominators_amount_value: 90,
+            nominators_quantity: 2,
+            points: Points::default(),
+            credited_amount_value: Some(90),
+            nominators: Vec::new(),
+        };
+        assert_eq!(v.expected_credited_amount(&payout), 100);
+        v.reward_destination = Some(RewardDestination::Stash);
+        assert_eq!(v.expected_credited_amount(&payout), 100);
+        v.reward_destination = Some(RewardDestination::Staked);
+        assert_eq!(v.expected_credited_amount(&payout), 90);
+        v.reward_destination = Some(RewardDestination::None);
+        assert_eq!(v.expected_credited_amount(&payout), 90);
+    }
+
+    #[test]
+    fn it_notes_high_commission_validators() {
+        let mut v = Validator::new(AccountId32::from([1; 32]));
+        assert_eq!(v.high_commission_note(100), None);
+        v.commission = Some(50_000_000);
+        assert_eq!(v.high_commission_note(100), None);
+        assert_eq!(v.high_commission_note(10), None);
+        assert_eq!(
+            v.high_commission_note(5),
+            Some("ℹ️ Commission at 5%, nominators receive little rewards".to_string())
+        );
+        v.commission = Some(1_000_000_000);
+        assert_eq!(
+            v.high_commission_note(100),
+            Some("ℹ️ Commission at 100%, nominators receive no rewards".to_string())
+        );
+    }
+
+    #[test]
+    fn it_reconciles_previous_era_payouts() {
+        let alice = AccountId32::from([1; 32]);
+        let bob = AccountId32::from([2; 32]);
+        let charlie = AccountId32::from([3; 32]);
+
+        // alice was already claimed, bob is still unclaimed
+        let mut v1 = Validator::new(alice);
+        v1.claimed.push((99, 0));
+        let mut v2 = Validator::new(bob);
+        v2.unclaimed.push((99, 0));
+        v2.unclaimed.push((98, 0));
+        // charlie had no rewards in era 99
+        let mut v3 = Validator::new(charlie);
+        v3.claimed.push((98, 0));
+        let validators = vec![v1, v2, v3];
+
+        assert_eq!(count_validators_with_rewards_in_era(&validators, 99), 2);
+        assert_eq!(count_validators_claimed_in_era(&validators, 99), 1);
+    }
+
+    #[test]
+    fn it_strips_emoji_and_truncates_identities() {
+        assert_eq!(strip_emoji("🐝 TURBOFLAKES 🚀/01"), "TURBOFLAKES /01");
+        assert_eq!(strip_emoji("节点 ✨ 01"), "节点 01");
+        assert_eq!(truncate_identity("TURBOFLAKES/01", 11), "TURBOFLAKES…");
+        assert_eq!(truncate_identity("TURBO FLAKES", 6), "TURBO…");
+        assert_eq!(truncate_identity("TURBOFLAKES", 0), "TURBOFLAKES");
+    }
+
+    #[test]
+    fn it_detects_uneventful_runs() {
+        let mut data = RawData {
+            network: Network {
+                active_era: 100,
+                name: "Polkadot".to_string(),
+                token_symbol: "DOT".to_string(),
+                token_decimals: 10,
+                token_price: None,
+            },
+            signer_details: SignerDetails {
+                account: AccountId32::from([0; 32]),
+                name: "signer".to_string(),
+                warnings: Vec::new(),
+                topup_amount: None,
+            },
+            validators: vec![Validator::new(AccountId32::from([1; 32]))],
+            payout_summary: PayoutSummary::default(),
+            pools_summary: None,
+        };
+        assert!(is_uneventful(&data));
+
+        data.validators[0]
+            .warnings
+            .push("No controller bonded!".to_string());
+        assert!(!is_uneventful(&data));
+
+        data.validators[0].warnings.clear();
+        data.payout_summary.calls_failed = 1;
+        assert!(!is_uneventful(&data));
+    }
+
+    #[test]
+    fn it_redacts_accounts_and_extrinsics_from_the_public_report() {
+        let signer = AccountId32::from([0; 32]);
+        let stash = AccountId32::from([1; 32]);
+        let extrinsic = H256::repeat_byte(0xab);
+        let pool_extrinsic = H256::repeat_byte(0xcd);
+        let mut validator = Validator::new(stash.clone());
+        validator.name = "ALICE".to_string();
+        validator.claimed.push((99, 0));
+        validator.payouts.push(Payout {
+            block_number: 1,
+            extrinsic,
+            era_index: 99,
+            validator_amount_value: 10,
+            nominators_amount_value: 90,
+            nominators_quantity: 2,
+            points: Points::default(),
+            credited_amount_value: None,
+            nominators: Vec::new(),
+        });
+        let data = RawData {
+            network: Network {
+                active_era: 100,
+                name: "Polkadot".to_string(),
+                token_symbol: "DOT".to_string(),
+                token_decimals: 10,
+                token_price: None,
+            },
+            signer_details: SignerDetails {
+                account: signer.clone(),
+                name: signer.to_string(),
+                warnings: Vec::new(),
+                topup_amount: None,
+            },
+            validators: vec![validator],
+            payout_summary: PayoutSummary {
+                calls: 2,
+                calls_succeeded: 1,
+                calls_failed: 1,
+                batches_failed: vec![FailedBatch {
+                    block_number: 2,
+                    extrinsic,
+                    calls: 1,
+                    error: "BadOrigin".to_string(),
+                }],
+                ..Default::default()
+            },
+            pools_summary: Some(NominationPoolsSummary {
+                calls: 3,
+                calls_succeeded: 1,
+                calls_failed: 2,
+                total_members: 3,
+                batches: vec![Batch {
+                    block_number: 3,
+                    extrinsic: pool_extrinsic,
+                }],
+                batches_failed: vec![FailedBatch {
+                    block_number: 3,
+                    extrinsic: pool_extrinsic,
+                    calls: 2,
+                    error: "BadOrigin".to_string(),
+                }],
+                ..Default::default()
+            }),
+        };
+        let network = data.network.clone();
+        let pools_summary = data.pools_summary.clone().unwrap();
+
+        let report = Report::from(data);
+        let public = report.public_formatted_message();
+        assert!(public.contains("ALICE"));
+        assert!(!public.contains(&stash.to_string()));
+        assert!(!public.contains(&signer.to_string()));
+        assert!(!public.contains(&extrinsic.to_string()));
+        assert!(!public.contains(&format!("{:?}", extrinsic)));
+        assert!(!public.contains("<a href"));
+        assert!(report.formatted_message().contains(&extrinsic.to_string()));
+        assert!(report
+            .formatted_message()
+            .contains(&format!("{:?}", extrinsic)));
+
+        // pools compound info sent as its own message
+        let report = Report::from_pools(&network, pools_summary);
+        let public = report.public_formatted_message();
+        assert!(public.contains("compounded"));
+        assert!(!public.contains(&pool_extrinsic.to_string()));
+        assert!(!public.contains(&format!("{:?}", pool_extrinsic)));
+        assert!(!public.contains("<a href"));
+        assert!(report
+            .formatted_message()
+            .contains(&format!("{:?}", pool_extrinsic)));
+    }
+
+    #[test]
+    fn it_removes_emoji_in_boring_style() {
+        let mut report = Report::new();
+        report
+            .add_raw_text("💙 <b>Polkadot</b> is playing era <i>100</i> 🎶 ".to_string());
+        report.add_raw_text("🚨 Validator is chilled 🚨".to_string());
+
+        report.boring = false;
+        assert_eq!(
+            report.formatted_message(),
+            "💙 <b>Polkadot</b> is playing era <i>100</i> 🎶 <br>🚨 Validator is chilled 🚨"
+        );
+
+        report.boring = true;
+        assert_eq!(
+            report.formatted_message(),
+            "<b>Polkadot</b> is playing era <i>100</i><br>Validator is chilled"
+        );
+        assert_eq!(
+            report.public_message(),
+            "<b>Polkadot</b> is playing era <i>100</i>\nValidator is chilled"
+        );
+    }
 }
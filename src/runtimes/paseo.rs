@@ -19,34 +19,42 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-use crate::config::CONFIG;
+use crate::config::{parse_era_pages, BatchSizeStrategy, StashSource, CONFIG};
 use crate::crunch::{
-    get_account_id_from_storage_key, get_keypair_from_seed_file, random_wait,
-    try_fetch_onet_data, try_fetch_stashes_from_remote_url, Crunch, NominatorsAmount,
-    ValidatorAmount, ValidatorIndex,
+    check_era_log_gaps, filter_pool_members_due, get_account_id_from_storage_key,
+    get_actual_fee_paid, get_balance_deposit, get_era_index_end,
+    get_keypair_from_seed_file, get_maximum_payouts_overrides, get_signer_from_config,
+    get_token_decimals, get_token_symbol, is_group_identity_engaged, is_paused,
+    is_within_min_run_interval, log_event, next_batch_range, random_wait,
+    record_era_processed, record_pool_members_compounded, rotate_stashes_window,
+    swap_previous_run, try_fetch_block_hash_from_config, try_fetch_onet_data,
+    try_fetch_stashes_from_remote_url, try_fetch_token_price, with_scan_retries, Crunch,
+    CrunchSigner, NominatorsAmount, ValidatorAmount, ValidatorIndex,
+    HISTORY_DEPTH_PLAUSIBLE_RANGE,
 };
 use crate::errors::CrunchError;
 use crate::pools::{nomination_pool_account, AccountType};
 use crate::report::{
-    Batch, EraIndex, Network, NominationPoolsSummary, PageIndex, Payout, PayoutSummary,
-    Points, RawData, Report, SignerDetails, Validator, Validators,
+    Batch, EraIndex, FailedBatch, Network, NominationPoolsSummary, PageIndex, Payout,
+    PayoutSummary, Points, RawData, Report, SignerDetails, StashSummary, Validator,
+    Validators,
 };
-use crate::{report, stats};
+use crate::{export, metrics, report, stats};
 use async_recursion::async_recursion;
+use futures::{stream, StreamExt};
 use log::{debug, info, warn};
 use std::{
-    cmp, convert::TryFrom, convert::TryInto, result::Result, str::FromStr, thread, time,
+    cmp, collections::HashMap, convert::TryFrom, convert::TryInto, mem, ops::Range,
+    result::Result, str::FromStr, thread, time,
 };
 use subxt::{
     config::polkadot::PolkadotExtrinsicParamsBuilder as TxParams,
     error::DispatchError,
     ext::codec::{Decode, Encode},
-    tx::TxStatus,
-    utils::{AccountId32, MultiAddress},
+    tx::{Payload, TxStatus},
+    utils::{AccountId32, MultiAddress, H256},
 };
 
-use subxt_signer::sr25519::Keypair;
-
 pub const PASEO_SPEC: &str = include_str!("../../chain_specs/paseo.json");
 pub const PEOPLE_PASEO_SPEC: &str = include_str!("../../chain_specs/people-paseo.json");
 
@@ -57,8 +65,10 @@ pub const PEOPLE_PASEO_SPEC: &str = include_str!("../../chain_specs/people-paseo
 mod node_runtime {}
 
 use node_runtime::{
+    nomination_pools::events::Bonded,
     runtime_types::bounded_collections::bounded_vec::BoundedVec,
     runtime_types::pallet_nomination_pools::{BondExtra, ClaimPermission},
+    runtime_types::pallet_staking::RewardDestination,
     staking::events::EraPaid,
     staking::events::PayoutStarted,
     staking::events::Rewarded,
@@ -81,18 +91,25 @@ type StakingCall = node_runtime::runtime_types::pallet_staking::pallet::pallet::
 type NominationPoolsCall =
     node_runtime::runtime_types::pallet_nomination_pools::pallet::Call;
 type UtilityCall = node_runtime::runtime_types::pallet_utility::pallet::Call;
+type RuntimeDispatchInfo =
+    node_runtime::runtime_types::pallet_transaction_payment::types::RuntimeDispatchInfo<
+        u128,
+        node_runtime::runtime_types::sp_weights::weight_v2::Weight,
+    >;
 
 pub async fn run_and_subscribe_era_paid_events(
     crunch: &Crunch,
 ) -> Result<(), CrunchError> {
+    let config = CONFIG.clone();
     info!("Inspect and `crunch` unclaimed payout rewards");
     // Run once before start subscription
     try_crunch(&crunch).await?;
+    let mut latest_run: Option<time::Instant> = Some(time::Instant::now());
     let mut latest_block_number_processed: Option<u32> = Some(0);
     info!("Subscribe 'EraPaid' on-chain finalized event");
     let api = crunch.client().clone();
     let mut block_sub = api.blocks().subscribe_finalized().await?;
-    while let Some(block) = block_sub.next().await {
+    while let Some(block) = crunch.next_or_keep_alive(&mut block_sub).await? {
         // let block = block?;
 
         // Silently handle RPC disconnection and wait for the next block as soon as reconnection is available
@@ -125,10 +142,19 @@ pub async fn run_and_subscribe_era_paid_events(
 
                         // Event --> staking::EraPaid
                         if let Some(_event) = events.find_first::<EraPaid>()? {
-                            let wait: u64 = random_wait(240);
-                            info!("Waiting {} seconds before run batch", wait);
-                            thread::sleep(time::Duration::from_secs(wait));
-                            try_crunch(&crunch).await?;
+                            if !is_within_min_run_interval(latest_run) {
+                                let wait: u64 = random_wait(
+                                    config.min_random_wait,
+                                    config.max_random_wait,
+                                );
+                                info!(
+"Waiting {} seconds (between {} and {}) before run batch",
+wait, config.min_random_wait, config.max_random_wait
+);
+                                thread::sleep(time::Duration::from_secs(wait));
+                                try_crunch(&crunch).await?;
+                                latest_run = Some(time::Instant::now());
+                            }
                         }
                     }
                 }
@@ -141,10 +167,17 @@ pub async fn run_and_subscribe_era_paid_events(
 
         // Event --> staking::EraPaid
         if let Some(_event) = events.find_first::<EraPaid>()? {
-            let wait: u64 = random_wait(240);
-            info!("Waiting {} seconds before run batch", wait);
-            thread::sleep(time::Duration::from_secs(wait));
-            try_crunch(&crunch).await?;
+            if !is_within_min_run_interval(latest_run) {
+                let wait: u64 =
+                    random_wait(config.min_random_wait, config.max_random_wait);
+                info!(
+                    "Waiting {} seconds (between {} and {}) before run batch",
+                    wait, config.min_random_wait, config.max_random_wait
+                );
+                thread::sleep(time::Duration::from_secs(wait));
+                try_crunch(&crunch).await?;
+                latest_run = Some(time::Instant::now());
+            }
         }
 
         latest_block_number_processed = Some(block.number());
@@ -157,8 +190,13 @@ pub async fn try_crunch(crunch: &Crunch) -> Result<(), CrunchError> {
     let config = CONFIG.clone();
     let api = crunch.client().clone();
 
-    let signer_keypair: Keypair = get_keypair_from_seed_file()?;
-    let seed_account_id: AccountId32 = signer_keypair.public_key().into();
+    // Skip the run while the pause sentinel file exists
+    if is_paused() {
+        return Ok(());
+    }
+
+    let signer: CrunchSigner = get_signer_from_config()?;
+    let seed_account_id: AccountId32 = signer.account_id();
 
     // Get signer account identity
     let (signer_name, _, _) = get_display_name(&crunch, &seed_account_id, None).await?;
@@ -166,9 +204,13 @@ pub async fn try_crunch(crunch: &Crunch) -> Result<(), CrunchError> {
         account: seed_account_id.clone(),
         name: signer_name,
         warnings: Vec::new(),
+        topup_amount: None,
     };
     info!("signer_details {:?}", signer_details);
 
+    // Top up the signer account from the funding account before any balance check
+    signer_details.topup_amount = try_topup_signer(&crunch, &seed_account_id).await?;
+
     // Warn if signer account is running low on funds (if lower than 2x Existential Deposit)
     let ed_addr = node_runtime::constants().balances().existential_deposit();
     let ed = api.constants().at(&ed_addr)?;
@@ -182,6 +224,7 @@ pub async fn try_crunch(crunch: &Crunch) -> Result<(), CrunchError> {
         .fetch(&seed_account_info_addr)
         .await?
     {
+        metrics::set_signer_balance(seed_account_info.data.free);
         if seed_account_info.data.free
             <= (config.existential_deposit_factor_warning as u128 * ed)
         {
@@ -189,13 +232,78 @@ pub async fn try_crunch(crunch: &Crunch) -> Result<(), CrunchError> {
             signer_details.warnings.push(warning.to_string());
             warn!("{warning}");
         }
+
+        // NOTE: payout_stakers is permissionless, so the signer does not need to be the
+        // stash or the controller of any validator, but it is the account paying the fees.
+        // Estimate the fee of a single payout call and warn if the signer can not afford it.
+        // The call is estimated unsigned, so that no signature is requested on every run.
+        let call = Call::Staking(StakingCall::payout_stakers {
+            validator_stash: seed_account_id.clone(),
+            era: 0,
+        });
+        let encoded = batch_call(&crunch, vec![call]).encode();
+        let fee = query_call_info(&crunch, &encoded).await?.partial_fee;
+        let spendable = seed_account_info
+            .data
+            .free
+            .saturating_sub(cmp::max(seed_account_info.data.frozen, ed));
+        debug!(
+            "signer spendable balance {} fee estimate {}",
+            spendable, fee
+        );
+        if spendable < fee {
+            let warning = "⚡ Signer account can not afford the fees of a payout call ⚡";
+            signer_details.warnings.push(warning.to_string());
+            warn!("{warning}");
+        }
+
+        // NOTE: If the signer is itself a bonded stash, payout fees compete with its own
+        // operational needs. Warn if its non-staked balance is thin.
+        let bonded_addr = node_runtime::storage().staking().bonded(&seed_account_id);
+        let is_bonded = api
+            .storage()
+            .at_latest()
+            .await?
+            .fetch(&bonded_addr)
+            .await?
+            .is_some();
+        if is_bonded {
+            let validators_addr = node_runtime::storage()
+                .staking()
+                .validators(&seed_account_id);
+            let is_validator = api
+                .storage()
+                .at_latest()
+                .await?
+                .fetch(&validators_addr)
+                .await?
+                .is_some();
+            debug!(
+                "signer account is bonded (validator: {}) with spendable balance {}",
+                is_validator, spendable
+            );
+            if spendable <= (config.existential_deposit_factor_warning as u128 * ed) {
+                let warning = if is_validator {
+                    "⚡ Signer account is a validator stash with little unstaked balance left for fees ⚡"
+                } else {
+                    "⚡ Signer account is a bonded stash with little unstaked balance left for fees ⚡"
+                };
+                signer_details.warnings.push(warning.to_string());
+                warn!("{warning}");
+            }
+        }
     } else {
+        // NOTE: Abort before scanning stashes since any payout would fail at submission
         let chain_name = crunch.rpc().system_chain().await?;
-        warn!("Signer account {seed_account_id} not found on the {chain_name} network!");
+        return Err(CrunchError::SignerNotFound(
+            seed_account_id.to_string(),
+            chain_name,
+        ));
     }
 
     // Get Network name
     let chain_name = crunch.rpc().system_chain().await?;
+    metrics::set_network(&chain_name);
 
     // Get Era index
     let active_era_addr = node_runtime::storage().staking().active_era();
@@ -209,27 +317,30 @@ pub async fn try_crunch(crunch: &Crunch) -> Result<(), CrunchError> {
         Some(info) => info.index,
         None => return Err(CrunchError::Other("Active era not available".into())),
     };
-
-    let properties = crunch.rpc().system_properties().await?;
-
-    // Get Token symbol
-    let token_symbol: String = if let Some(token_symbol) = properties.get("tokenSymbol") {
-        token_symbol.as_str().unwrap_or_default().to_string()
-    } else {
-        "ND".to_string()
+    metrics::set_active_era(active_era_index);
+
+    // NOTE: system_properties is an optional RPC method, if not available fall back
+    // to the known token symbol and decimals of the network
+    let (token_symbol, token_decimals) = match crunch.rpc().system_properties().await {
+        Ok(properties) => {
+            // Get Token symbol and decimals of the native token
+            let token_symbol: String =
+                get_token_symbol(&properties).unwrap_or_else(|| "ND".to_string());
+            let token_decimals: u8 = get_token_decimals(&properties).unwrap_or(12);
+            (token_symbol, token_decimals)
+        }
+        Err(e) => {
+            let runtime = crunch.runtime();
+            warn!(
+                "system_properties not available ({e}), using known {} token symbol and decimals instead",
+                runtime
+            );
+            (runtime.token_symbol().to_string(), runtime.token_decimals())
+        }
     };
 
-    // Get Token decimals
-    let token_decimals: u8 = if let Some(token_decimals) = properties.get("tokenDecimals")
-    {
-        token_decimals
-            .as_u64()
-            .unwrap_or_default()
-            .try_into()
-            .unwrap()
-    } else {
-        12
-    };
+    // Get Token price in fiat (once per run)
+    let token_price = try_fetch_token_price(chain_name.clone()).await?;
 
     // Set network info
     let network = Network {
@@ -237,18 +348,33 @@ pub async fn try_crunch(crunch: &Crunch) -> Result<(), CrunchError> {
         active_era: active_era_index,
         token_symbol,
         token_decimals,
+        token_price,
     };
     debug!("network {:?}", network);
 
-    // Check if group by identity is enabled by user to change the behaviour of how stashes are processed
-    if config.group_identity_enabled {
-        // Try run payouts in batches
-        let mut all_validators =
-            collect_validators_data(&crunch, active_era_index).await?;
+    // Check for eras not processed since the last run
+    check_era_log_gaps(active_era_index.saturating_sub(1));
+
+    // Check that all configured pool ids exist
+    try_check_pool_ids(&crunch, chain_name.clone()).await?;
+
+    // Get stashes to be processed in this run
+    let (stashes, stashes_window) = rotate_stashes_window(get_stashes(&crunch).await?)?;
 
-        let parent_identities: Vec<String> =
-            get_distinct_parent_identites(all_validators.clone());
+    // NOTE: Explicit era pages defined by the user are claimed without scanning eras in chunks
+    let scan_by_era_chunks = config.era_chunk_size > 0 && config.only_pages.is_empty();
 
+    let mut all_validators = if scan_by_era_chunks {
+        collect_validators_info(&crunch, &stashes).await?
+    } else {
+        collect_validators_data(&crunch, &stashes, active_era_index).await?
+    };
+
+    let parent_identities: Vec<String> =
+        get_distinct_parent_identites(all_validators.clone());
+
+    // Check if group by identity is enabled by user or engaged by the number of identities to change the behaviour of how stashes are processed
+    if is_group_identity_engaged(parent_identities.len()) {
         for parent in parent_identities {
             // Filter validators by parent identity
             let mut validators = all_validators
@@ -264,9 +390,36 @@ pub async fn try_crunch(crunch: &Crunch) -> Result<(), CrunchError> {
 
             if validators.len() > 0 {
                 // Try run payouts in batches
-                let payout_summary =
-                    try_run_batch_payouts(&crunch, &signer_keypair, &mut validators)
-                        .await?;
+                let mut payout_summary = if scan_by_era_chunks {
+                    try_run_batch_payouts_by_era_chunks(
+                        &crunch,
+                        &signer,
+                        &mut validators,
+                        active_era_index,
+                    )
+                    .await?
+                } else {
+                    try_run_batch_payouts(
+                        &crunch,
+                        &signer,
+                        &mut validators,
+                        active_era_index,
+                    )
+                    .await?
+                };
+                payout_summary.stashes_window = stashes_window.clone();
+                payout_summary.grouped_by_identity = true;
+                metrics::add_payout_summary(
+                    active_era_index.saturating_sub(1),
+                    &payout_summary,
+                );
+                record_era_processed(
+                    active_era_index.saturating_sub(1),
+                    &validators,
+                    &payout_summary,
+                );
+                payout_summary.previous_run =
+                    swap_previous_run(&parent, &validators, &payout_summary);
 
                 // Try fetch ONE-T grade data
                 for v in &mut validators {
@@ -276,16 +429,15 @@ pub async fn try_crunch(crunch: &Crunch) -> Result<(), CrunchError> {
                 }
 
                 // NOTE: In the last iteration try to batch pools if any and include them in the report
-                // TODO: Eventually we could do a separate message containing only the pools report
                 let pools_summary: Option<NominationPoolsSummary> =
                     if all_validators.len() == 0 {
                         // Try run pool members in batches
-                        Some(try_run_batch_pool_members(&crunch, &signer_keypair).await?)
+                        Some(try_run_batch_pool_members(&crunch, &signer).await?)
                     } else {
                         None
                     };
 
-                let data = RawData {
+                let mut data = RawData {
                     network: network.clone(),
                     signer_details: signer_details.clone(),
                     validators,
@@ -293,25 +445,46 @@ pub async fn try_crunch(crunch: &Crunch) -> Result<(), CrunchError> {
                     pools_summary,
                 };
 
+                export::try_append(&data);
+                let pools_report = split_pools_report(&mut data);
                 let report = Report::from(data);
-                crunch
-                    .send_message(&report.message(), &report.formatted_message())
-                    .await?;
+                crunch.send_report(&report).await?;
+                if let Some(pools_report) = pools_report {
+                    crunch.send_report(&pools_report).await?;
+                }
             }
             // NOTE: To prevent too many request from matrix API set a sleep here of 5 seconds before trying another identity payout
             thread::sleep(time::Duration::from_secs(5));
         }
     } else {
-        let mut validators = collect_validators_data(&crunch, active_era_index).await?;
-
         // Try run payouts in batches
-        let payout_summary =
-            try_run_batch_payouts(&crunch, &signer_keypair, &mut validators).await?;
+        let mut validators = all_validators;
+        let mut payout_summary = if scan_by_era_chunks {
+            try_run_batch_payouts_by_era_chunks(
+                &crunch,
+                &signer,
+                &mut validators,
+                active_era_index,
+            )
+            .await?
+        } else {
+            try_run_batch_payouts(&crunch, &signer, &mut validators, active_era_index)
+                .await?
+        };
+        payout_summary.stashes_window = stashes_window;
+        metrics::add_payout_summary(active_era_index.saturating_sub(1), &payout_summary);
+        metrics::set_pending_unclaimed(&validators);
+        record_era_processed(
+            active_era_index.saturating_sub(1),
+            &validators,
+            &payout_summary,
+        );
+        payout_summary.previous_run = swap_previous_run("", &validators, &payout_summary);
 
         // Try run members in batches
-        let pools_summary = try_run_batch_pool_members(&crunch, &signer_keypair).await?;
+        let pools_summary = try_run_batch_pool_members(&crunch, &signer).await?;
 
-        let data = RawData {
+        let mut data = RawData {
             network,
             signer_details,
             validators,
@@ -319,26 +492,63 @@ pub async fn try_crunch(crunch: &Crunch) -> Result<(), CrunchError> {
             pools_summary: Some(pools_summary),
         };
 
+        export::try_append(&data);
+        let pools_report = split_pools_report(&mut data);
         let report = Report::from(data);
-        crunch
-            .send_message(&report.message(), &report.formatted_message())
-            .await?;
+        crunch.send_report(&report).await?;
+        if let Some(pools_report) = pools_report {
+            crunch.send_report(&pools_report).await?;
+        }
     }
 
+    metrics::set_last_run(active_era_index.saturating_sub(1));
+
     Ok(())
 }
 
+/// Takes the pools summary out of the run data to be sent as its own report,
+/// if enabled by the user and there are pool rewards to report
+fn split_pools_report(data: &mut RawData) -> Option<Report> {
+    let config = CONFIG.clone();
+    if !config.report_split_pools {
+        return None;
+    }
+    data.pools_summary
+        .take()
+        .filter(|pools_summary| pools_summary.total_members > 0)
+        .map(|pools_summary| Report::from_pools(&data.network, pools_summary))
+}
+
 pub async fn try_run_batch_pool_members(
     crunch: &Crunch,
-    signer: &Keypair,
+    signer: &CrunchSigner,
 ) -> Result<NominationPoolsSummary, CrunchError> {
     let config = CONFIG.clone();
     let api = crunch.client().clone();
 
     let mut calls_for_batch: Vec<Call> = vec![];
+    let mut members_for_batch: Vec<AccountId32> = vec![];
+    let mut members_compounded: Vec<AccountId32> = vec![];
     let mut summary: NominationPoolsSummary = Default::default();
 
+    // Get Active Era index to track the era members are compounded in
+    let active_era_addr = node_runtime::storage().staking().active_era();
+    let active_era_index = match api
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(&active_era_addr)
+        .await?
+    {
+        Some(info) => info.index,
+        None => return Err(CrunchError::Other("Active era not available".into())),
+    };
+
     if let Some(members) = try_fetch_pool_members_for_compound(&crunch).await? {
+        // Skip members compounded within the minimum interval of eras defined by the user
+        let total = members.len();
+        let members = filter_pool_members_due(members, active_era_index);
+        summary.members_not_due = (total - members.len()) as u32;
         //
         for member in &members {
             //
@@ -347,164 +557,369 @@ pub async fn try_run_batch_pool_members(
                 extra: BondExtra::Rewards,
             });
             calls_for_batch.push(call);
+            members_for_batch.push(member.clone());
             summary.calls += 1;
         }
         summary.total_members = members.len() as u32;
     }
 
     if calls_for_batch.len() > 0 {
-        // TODO check batch call weight or maximum_calls [default: 8]
-        //
-        // Calculate the number of extrinsics (iteractions) based on the maximum number of calls per batch
-        // and the number of calls to be sent
-        //
-        let maximum_batch_calls = (calls_for_batch.len() as f32
-            / config.maximum_pool_members_calls as f32)
-            .ceil() as u32;
-        let mut iteration = Some(0);
-        while let Some(x) = iteration {
-            if x == maximum_batch_calls {
-                iteration = None;
-            } else {
-                let call_start_index: usize =
-                    (x * config.maximum_pool_members_calls).try_into().unwrap();
-                let call_end_index: usize = if config.maximum_pool_members_calls
-                    > calls_for_batch[call_start_index..].len() as u32
-                {
-                    ((x * config.maximum_pool_members_calls)
-                        + calls_for_batch[call_start_index..].len() as u32)
-                        .try_into()
-                        .unwrap()
-                } else {
-                    ((x * config.maximum_pool_members_calls)
-                        + config.maximum_pool_members_calls)
-                        .try_into()
-                        .unwrap()
-                };
+        let mut call_start_index: usize = 0;
+        while call_start_index < calls_for_batch.len() {
+            // Calculate the number of calls to be sent in the next batch based on the batch size strategy
+            let batch_size = validate_calls_for_batch(
+                &crunch,
+                &calls_for_batch[call_start_index..],
+                crunch.maximum_pool_members_calls(),
+            )
+            .await?;
+            let batch_range =
+                next_batch_range(call_start_index, batch_size, calls_for_batch.len());
 
-                debug!(
-                    "batch pool_members_calls indexes [{:?} : {:?}]",
-                    call_start_index, call_end_index
-                );
+            debug!(
+                "batch pool_members_calls indexes [{:?} : {:?}]",
+                batch_range.start, batch_range.end
+            );
 
-                let calls_for_batch_clipped =
-                    calls_for_batch[call_start_index..call_end_index].to_vec();
-
-                // Note: Unvalidated extrinsic. If it fails a static metadata file will need to be updated!
-                let tx = node_runtime::tx()
-                    .utility()
-                    .force_batch(calls_for_batch_clipped.clone())
-                    .unvalidated();
-
-                // Configure the transaction parameters by defining `tip` and `tx_mortal` as per user config;
-                let tx_params = if config.tx_mortal_period > 0 {
-                    // Get latest block to be submitted in tx params
-                    let latest_block = api.blocks().at_latest().await?;
-                    TxParams::new()
-                        .tip(config.tx_tip.into())
-                        .mortal(latest_block.header(), config.tx_mortal_period)
-                        .build()
+            // NOTE: Pending rewards might have been claimed elsewhere since they were fetched,
+            // so they are checked again just before submission to avoid calls with nothing to bond
+            let mut calls_for_batch_clipped: Vec<Call> = Vec::new();
+            for (call, member) in calls_for_batch[batch_range.clone()]
+                .iter()
+                .zip(members_for_batch[batch_range.clone()].iter())
+            {
+                if fetch_pool_member_pending_rewards(&crunch, member).await? == 0 {
+                    info!("{} * No rewards left to compound, call skipped", member);
+                    summary.calls_no_rewards += 1;
                 } else {
-                    TxParams::new().tip(config.tx_tip.into()).build()
-                };
+                    calls_for_batch_clipped.push(call.clone());
+                }
+            }
 
-                // Log call data in debug mode
-                if config.is_debug {
-                    let batch_call = Call::Utility(UtilityCall::force_batch {
-                        calls: calls_for_batch_clipped.clone(),
-                    });
+            if calls_for_batch_clipped.is_empty() {
+                call_start_index = batch_range.end;
+                continue;
+            }
 
-                    debug!("call_data: 0x{}", hex::encode(batch_call.encode()));
-                }
+            // Log the batch instead of signing and submitting it
+            if config.dry_run_enabled {
+                log_dry_run_batch(&crunch, calls_for_batch_clipped).await?;
+                call_start_index = batch_range.end;
+                continue;
+            }
+
+            // Note: Unvalidated extrinsic. If it fails a static metadata file will need to be updated!
+            let tx = batch_payload(&crunch, calls_for_batch_clipped.clone());
+
+            // Configure the transaction parameters by defining `tip` and `tx_mortal` as per user config;
+            let tx_params = if config.tx_mortal_period > 0 {
+                // Get latest block to be submitted in tx params
+                let latest_block = api.blocks().at_latest().await?;
+                TxParams::new()
+                    .tip(config.tx_tip.into())
+                    .mortal(latest_block.header(), crunch.tx_mortal_period())
+                    .build()
+            } else {
+                TxParams::new().tip(config.tx_tip.into()).build()
+            };
+
+            // Log call data in debug mode
+            if config.is_debug {
+                let batch_call = batch_call(&crunch, calls_for_batch_clipped.clone());
 
-                let mut tx_progress = api
-                    .tx()
-                    .sign_and_submit_then_watch(&tx, signer, tx_params)
-                    .await?;
-
-                while let Some(status) = tx_progress.next().await {
-                    match status? {
-                        TxStatus::InFinalizedBlock(in_block) => {
-                            // Get block number
-                            let block_number = if let Some(header) = crunch
-                                .rpc()
-                                .chain_get_header(Some(in_block.block_hash()))
-                                .await?
+                debug!("call_data: 0x{}", hex::encode(batch_call.encode()));
+            }
+
+            let mut tx_progress = signer
+                .sign_and_submit_then_watch(&api, &tx, tx_params)
+                .await?;
+
+            while let Some(status) = tx_progress.next().await {
+                match status? {
+                    TxStatus::InFinalizedBlock(in_block) => {
+                        // Get block number
+                        let block_number = if let Some(header) = crunch
+                            .rpc()
+                            .chain_get_header(Some(in_block.block_hash()))
+                            .await?
+                        {
+                            header.number
+                        } else {
+                            0
+                        };
+
+                        // Fetch events from block
+                        let tx_events = in_block.fetch_events().await?;
+
+                        // Iterate over events to calculate respective reward amounts
+                        for event in tx_events.iter() {
+                            let event = event?;
+                            log_event(&event)?;
+                            if let Some(_ev) = event.as_event::<ItemCompleted>()? {
+                                // https://polkadot.js.org/docs/substrate/events#itemcompleted
+                                // summary: A single item within a Batch of dispatches has completed with no error.
+                                //
+                                summary.calls_succeeded += 1;
+                            } else if let Some(_ev) = event.as_event::<ItemFailed>()? {
+                                // https://polkadot.js.org/docs/substrate/events/#itemfailedspruntimedispatcherror
+                                // summary: A single item within a Batch of dispatches has completed with error.
+                                //
+                                summary.calls_failed += 1;
+                            } else if let Some(ev) = event.as_event::<Bonded>()? {
+                                // https://polkadot.js.org/docs/substrate/events#bondedaccountid32-u32-u128-bool
+                                // summary: A member has became bonded in a pool.
+                                // NOTE: Compounded rewards are bonded by members that already joined the pool
+                                if !ev.joined {
+                                    summary.add_compounded(ev.pool_id, ev.bonded);
+                                    members_compounded.push(ev.member.clone());
+                                }
+                            } else if let Some(_ev) =
+                                event.as_event::<BatchCompleted>()?
                             {
-                                header.number
-                            } else {
-                                0
-                            };
-
-                            // Fetch events from block
-                            let tx_events = in_block.fetch_events().await?;
-
-                            // Iterate over events to calculate respective reward amounts
-                            for event in tx_events.iter() {
-                                let event = event?;
-                                if let Some(_ev) = event.as_event::<ItemCompleted>()? {
-                                    // https://polkadot.js.org/docs/substrate/events#itemcompleted
-                                    // summary: A single item within a Batch of dispatches has completed with no error.
-                                    //
-                                    summary.calls_succeeded += 1;
-                                } else if let Some(_ev) =
-                                    event.as_event::<ItemFailed>()?
-                                {
-                                    // https://polkadot.js.org/docs/substrate/events/#itemfailedspruntimedispatcherror
-                                    // summary: A single item within a Batch of dispatches has completed with error.
-                                    //
-                                    summary.calls_failed += 1;
-                                } else if let Some(_ev) =
-                                    event.as_event::<BatchCompleted>()?
-                                {
-                                    // https://polkadot.js.org/docs/substrate/events#batchcompleted
-                                    // summary: Batch of dispatches completed fully with no error.
-                                    info!(
-                            "Nomination Pools Compound Batch Completed ({} calls)",
-                            calls_for_batch_clipped.len()
-                        );
-                                    let b = Batch {
-                                        block_number,
-                                        extrinsic: tx_events.extrinsic_hash(),
-                                    };
-                                    summary.batches.push(b);
-                                } else if let Some(_ev) =
-                                    event.as_event::<BatchCompletedWithErrors>()?
-                                {
-                                    // https://polkadot.js.org/docs/substrate/events/#batchcompletedwitherrors
-                                    // summary: Batch of dispatches completed but has errors.
-                                    info!(
-                            "Nomination Pools Compound Batch Completed with errors ({} calls)",
-                            calls_for_batch_clipped.len()
-                        );
-                                    let b = Batch {
-                                        block_number,
-                                        extrinsic: tx_events.extrinsic_hash(),
-                                    };
-                                    summary.batches.push(b);
+                                // https://polkadot.js.org/docs/substrate/events#batchcompleted
+                                // summary: Batch of dispatches completed fully with no error.
+                                info!(
+                        "Nomination Pools Compound Batch Completed ({} calls)",
+                        calls_for_batch_clipped.len()
+                    );
+                                let b = Batch {
+                                    block_number,
+                                    extrinsic: tx_events.extrinsic_hash(),
+                                };
+                                summary.batches.push(b);
+                            } else if let Some(_ev) =
+                                event.as_event::<BatchCompletedWithErrors>()?
+                            {
+                                // https://polkadot.js.org/docs/substrate/events/#batchcompletedwitherrors
+                                // summary: Batch of dispatches completed but has errors.
+                                info!(
+                        "Nomination Pools Compound Batch Completed with errors ({} calls)",
+                        calls_for_batch_clipped.len()
+                    );
+                                let b = Batch {
+                                    block_number,
+                                    extrinsic: tx_events.extrinsic_hash(),
+                                };
+                                summary.batches.push(b);
+                            } else if let Some(ev) =
+                                event.as_event::<BatchInterrupted>()?
+                            {
+                                // https://polkadot.js.org/docs/substrate/events#batchinterruptedu32-spruntimedispatcherror
+                                // summary: Batch of dispatches did not complete fully. Index of first failing dispatch given, as well as the error.
+                                // NOTE: Only emitted by the `batch` fallback, the failing call and every call after it are not dispatched
+                                warn!("{:?}", ev);
+                                if let Some(batch) = FailedBatch::interrupted(
+                                    block_number,
+                                    tx_events.extrinsic_hash(),
+                                    calls_for_batch_clipped.len(),
+                                    ev.index,
+                                    format!("{:?}", ev.error),
+                                ) {
+                                    summary.add_batch_failed(batch);
                                 }
                             }
                         }
-                        TxStatus::Error { message } => {
-                            warn!("TxStatus: {message:?}");
-                        }
-                        TxStatus::Invalid { message } => {
-                            warn!("TxStatus: {message:?}");
-                        }
-                        TxStatus::Dropped { message } => {
-                            warn!("TxStatus: {message:?}");
-                        }
-                        _ => {}
                     }
+                    TxStatus::Error { message } => {
+                        warn!("TxStatus: {message:?}");
+                    }
+                    TxStatus::Invalid { message } => {
+                        warn!("TxStatus: {message:?}");
+                    }
+                    TxStatus::Dropped { message } => {
+                        warn!("TxStatus: {message:?}");
+                    }
+                    _ => {}
                 }
-                iteration = Some(x + 1);
             }
+            call_start_index = batch_range.end;
         }
     }
 
+    record_pool_members_compounded(&members_compounded, active_era_index);
+
     Ok(summary)
 }
 
+/// Transfer `signer_topup_amount` from the funding account to the signer account if the
+/// signer free balance is below `signer_topup_threshold`, returning the amount transferred
+async fn try_topup_signer(
+    crunch: &Crunch,
+    signer_account: &AccountId32,
+) -> Result<Option<u128>, CrunchError> {
+    let config = CONFIG.clone();
+    if config.funding_seed_path.is_empty()
+        || config.signer_topup_amount == 0
+        || config.dry_run_enabled
+    {
+        return Ok(None);
+    }
+    let api = crunch.client().clone();
+
+    let account_info_addr = node_runtime::storage().system().account(signer_account);
+    let free = api
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(&account_info_addr)
+        .await?
+        .map(|account_info| account_info.data.free)
+        .unwrap_or_default();
+    if free >= config.signer_topup_threshold as u128 {
+        return Ok(None);
+    }
+
+    let funding =
+        get_keypair_from_seed_file(&config.funding_seed_path, &config.keystore_password)?;
+    let amount = config.signer_topup_amount as u128;
+    info!(
+        "Signer free balance {} below the top-up threshold {}, transferring {} from the funding account",
+        free, config.signer_topup_threshold, amount
+    );
+    let tx = node_runtime::tx()
+        .balances()
+        .transfer_keep_alive(MultiAddress::Id(signer_account.clone()), amount);
+    let tx_events = api
+        .tx()
+        .sign_and_submit_then_watch_default(&tx, &funding)
+        .await?
+        .wait_for_finalized_success()
+        .await?;
+    info!(
+        "Signer topped up in extrinsic {:?}",
+        tx_events.extrinsic_hash()
+    );
+    Ok(Some(amount))
+}
+
+/// Returns the batch extrinsic of the calls. Runtimes without `force_batch` fall back
+/// to `batch`, which is interrupted at the first failing call
+fn batch_payload(crunch: &Crunch, calls: Vec<Call>) -> Box<dyn Payload + Send + Sync> {
+    if crunch.is_force_batch_available() {
+        Box::new(
+            node_runtime::tx()
+                .utility()
+                .force_batch(calls)
+                .unvalidated(),
+        )
+    } else {
+        Box::new(node_runtime::tx().utility().batch(calls).unvalidated())
+    }
+}
+
+/// Returns the batch call of the calls, as submitted by `batch_payload`
+fn batch_call(crunch: &Crunch, calls: Vec<Call>) -> Call {
+    if crunch.is_force_batch_available() {
+        Call::Utility(UtilityCall::force_batch { calls })
+    } else {
+        Call::Utility(UtilityCall::batch { calls })
+    }
+}
+
+/// Logs the call data and the payment info of the batch, without signing or submitting it
+async fn log_dry_run_batch(crunch: &Crunch, calls: Vec<Call>) -> Result<(), CrunchError> {
+    let calls_len = calls.len();
+    let encoded = batch_call(&crunch, calls).encode();
+    let info = query_call_info(&crunch, &encoded).await?;
+
+    info!(
+        "Dry run batch of {} calls -> estimated fee {} weight {:?}",
+        calls_len, info.partial_fee, info.weight
+    );
+    info!("Dry run call_data: 0x{}", hex::encode(&encoded));
+    Ok(())
+}
+
+/// Query the weight and fee of the encoded call from the TransactionPaymentCallApi,
+/// without the need to sign it.
+/// NOTE: The fee does not account for the length of the signature and signed extensions
+async fn query_call_info(
+    crunch: &Crunch,
+    encoded: &[u8],
+) -> Result<RuntimeDispatchInfo, CrunchError> {
+    let call_parameters = [encoded, &(encoded.len() as u32).encode()[..]].concat();
+    let info: RuntimeDispatchInfo = crunch
+        .client()
+        .runtime_api()
+        .at_latest()
+        .await?
+        .call_raw(
+            "TransactionPaymentCallApi_query_call_info",
+            Some(&call_parameters),
+        )
+        .await?;
+    Ok(info)
+}
+
+/// Returns the number of calls, from the start of `calls`, to be sent in the next batch
+/// based on the batch size strategy defined by the user.
+/// NOTE: `maximum_calls` is the upper bound of every strategy, the weight and fee
+/// strategies can only make the batch smaller
+async fn validate_calls_for_batch(
+    crunch: &Crunch,
+    calls: &[Call],
+    maximum_calls: u32,
+) -> Result<usize, CrunchError> {
+    let config = CONFIG.clone();
+    let api = crunch.client().clone();
+
+    let fixed_size = cmp::min(cmp::max(maximum_calls as usize, 1), calls.len());
+
+    let strategy = match config.batch_size_strategy {
+        BatchSizeStrategy::Fee if config.max_batch_fee == 0 => {
+            warn!("Maximum batch fee not defined, batch sized by maximum calls instead");
+            BatchSizeStrategy::Fixed
+        }
+        strategy => strategy,
+    };
+
+    if strategy == BatchSizeStrategy::Fixed {
+        return Ok(fixed_size);
+    }
+
+    // Get the maximum weight allowed for a single extrinsic
+    let block_weights_addr = node_runtime::constants().system().block_weights();
+    let max_extrinsic = api
+        .constants()
+        .at(&block_weights_addr)?
+        .per_class
+        .normal
+        .max_extrinsic;
+
+    // Increase the batch one call at a time while it still fits
+    let mut size = 1;
+    while size < fixed_size {
+        // NOTE: The batch is estimated unsigned, so that no signature is requested
+        // for every batch size tried
+        let encoded = batch_call(&crunch, calls[..size + 1].to_vec()).encode();
+        let info = query_call_info(&crunch, &encoded).await?;
+
+        let fits = match strategy {
+            BatchSizeStrategy::Fee => info.partial_fee <= config.max_batch_fee.into(),
+            _ => {
+                // NOTE: Batches only fill the fraction of the maximum weight defined by the user
+                let fraction = config.max_weight_fraction as f64;
+                match &max_extrinsic {
+                    Some(max) => {
+                        info.weight.ref_time <= (max.ref_time as f64 * fraction) as u64
+                            && info.weight.proof_size
+                                <= (max.proof_size as f64 * fraction) as u64
+                    }
+                    None => true,
+                }
+            }
+        };
+
+        if !fits {
+            break;
+        }
+        size += 1;
+    }
+
+    debug!("{:?} batch size strategy -> {} calls", strategy, size);
+    Ok(size)
+}
+
 //Provides a distinct and sorted vector of parent identities by string
 //where there are entries without identities, these are placed to the end of the vector
 pub fn get_distinct_parent_identites(validators: Validators) -> Vec<String> {
@@ -534,8 +949,9 @@ pub fn get_distinct_parent_identites(validators: Validators) -> Vec<String> {
 
 pub async fn try_run_batch_payouts(
     crunch: &Crunch,
-    signer: &Keypair,
+    signer: &CrunchSigner,
     validators: &mut Validators,
+    era_index: EraIndex,
 ) -> Result<PayoutSummary, CrunchError> {
     let config = CONFIG.clone();
     let api = crunch.client().clone();
@@ -545,16 +961,38 @@ pub async fn try_run_batch_payouts(
     // let mut validators = collect_validators_data(&crunch, active_era_index).await?;
     let mut summary: PayoutSummary = Default::default();
 
+    // Validators expected to have the previous era claimed by the end of the run
+    let previous_era = era_index.saturating_sub(1);
+    summary.previous_era_expected =
+        report::count_validators_with_rewards_in_era(validators, previous_era);
+
+    let maximum_payouts_overrides = get_maximum_payouts_overrides()?;
+
     for v in validators.into_iter() {
         //
         if v.unclaimed.len() > 0 {
-            let mut maximum_payouts = Some(config.maximum_payouts);
+            // NOTE: maximum_payouts 0 means unlimited
+            let maximum_payouts_value = maximum_payouts_overrides
+                .get(&v.stash)
+                .copied()
+                .unwrap_or(config.maximum_payouts);
+            let mut maximum_payouts = if maximum_payouts_value == 0 {
+                Some(v.unclaimed.len() as u32)
+            } else {
+                Some(maximum_payouts_value)
+            };
             // define extrinsic payout stakers calls as many as unclaimed eras or maximum_payouts reached
             while let Some(i) = maximum_payouts {
                 if i == 0 {
                     maximum_payouts = None;
+                } else if config.max_total_calls > 0
+                    && summary.calls >= config.max_total_calls
+                {
+                    // NOTE: Unclaimed eras beyond max_total_calls are carried to the next run
+                    summary.calls_deferred += cmp::min(i, v.unclaimed.len() as u32);
+                    maximum_payouts = None;
                 } else {
-                    if let Some((claim_era, _page_index)) = v.unclaimed.pop() {
+                    if let Some((claim_era, page_index)) = v.unclaimed.pop() {
                         // TODO: After deprecated storage items going away we could consider
                         // using payout_stakers_by_page with the respective page_index.
                         // Until than lets just call payout_stakers x times based on
@@ -562,10 +1000,19 @@ pub async fn try_run_batch_payouts(
                         //
                         // PR: https://github.com/paritytech/polkadot-sdk/pull/1189
                         //
-                        let call = Call::Staking(StakingCall::payout_stakers {
-                            validator_stash: v.stash.clone(),
-                            era: claim_era,
-                        });
+                        // NOTE: Explicit era pages defined by the user are claimed by page
+                        let call = if config.only_pages.is_empty() {
+                            Call::Staking(StakingCall::payout_stakers {
+                                validator_stash: v.stash.clone(),
+                                era: claim_era,
+                            })
+                        } else {
+                            Call::Staking(StakingCall::payout_stakers_by_page {
+                                validator_stash: v.stash.clone(),
+                                era: claim_era,
+                                page: page_index,
+                            })
+                        };
                         calls_for_batch.push(call);
                         summary.calls += 1;
                     }
@@ -579,254 +1026,298 @@ pub async fn try_run_batch_payouts(
     }
 
     if calls_for_batch.len() > 0 {
-        // TODO check batch call weight or maximum_calls [default: 4]
-        //
-        // Calculate the number of extrinsics (iteractions) based on the maximum number of calls per batch
-        // and the number of calls to be sent
-        //
-        let maximum_batch_calls =
-            (calls_for_batch.len() as f32 / config.maximum_calls as f32).ceil() as u32;
-        let mut iteration = Some(0);
-        while let Some(x) = iteration {
-            if x == maximum_batch_calls {
-                iteration = None;
-            } else {
-                let mut validator_index: ValidatorIndex = None;
-                let mut era_index: EraIndex = 0;
-                let mut validator_amount_value: ValidatorAmount = 0;
-                let mut nominators_amount_value: NominatorsAmount = 0;
-                let mut nominators_quantity = 0;
-
-                let call_start_index: usize =
-                    (x * config.maximum_calls).try_into().unwrap();
-                let call_end_index: usize = if config.maximum_calls
-                    > calls_for_batch[call_start_index..].len() as u32
-                {
-                    ((x * config.maximum_calls)
-                        + calls_for_batch[call_start_index..].len() as u32)
-                        .try_into()
-                        .unwrap()
-                } else {
-                    ((x * config.maximum_calls) + config.maximum_calls)
-                        .try_into()
-                        .unwrap()
-                };
+        let mut call_start_index: usize = 0;
+        while call_start_index < calls_for_batch.len() {
+            let mut validator_index: ValidatorIndex = None;
+            let mut era_index: EraIndex = 0;
+            let mut validator_amount_value: ValidatorAmount = 0;
+            let mut nominators_amount_value: NominatorsAmount = 0;
+            let mut nominators_quantity = 0;
+            let mut nominators: Vec<(AccountId32, u128)> = Vec::new();
+            let mut credited_amount_value: u128 = 0;
+
+            // Calculate the number of calls to be sent in the next batch based on the batch size strategy
+            let batch_size = validate_calls_for_batch(
+                &crunch,
+                &calls_for_batch[call_start_index..],
+                crunch.maximum_calls(),
+            )
+            .await?;
+            let batch_range =
+                next_batch_range(call_start_index, batch_size, calls_for_batch.len());
 
-                debug!(
-                    "batch call indexes [{:?} : {:?}]",
-                    call_start_index, call_end_index
-                );
+            debug!(
+                "batch call indexes [{:?} : {:?}]",
+                batch_range.start, batch_range.end
+            );
 
-                let calls_for_batch_clipped =
-                    calls_for_batch[call_start_index..call_end_index].to_vec();
-
-                // Note: Unvalidated extrinsic. If it fails a static metadata file will need to be updated!
-                let tx: subxt::tx::DefaultPayload<
-                    node_runtime::utility::calls::types::ForceBatch,
-                > = node_runtime::tx()
-                    .utility()
-                    .force_batch(calls_for_batch_clipped.clone())
-                    .unvalidated();
-
-                // Configure the transaction parameters by defining `tip` and `tx_mortal` as per user config;
-                let tx_params = if config.tx_mortal_period > 0 {
-                    // Get latest block to be submitted in tx params
-                    let latest_block = api.blocks().at_latest().await?;
-                    TxParams::new()
-                        .tip(config.tx_tip.into())
-                        .mortal(latest_block.header(), config.tx_mortal_period)
-                        .build()
-                } else {
-                    TxParams::new().tip(config.tx_tip.into()).build()
-                };
+            let calls_for_batch_clipped = calls_for_batch[batch_range.clone()].to_vec();
 
-                // Log call data in debug mode
-                if config.is_debug {
-                    let batch_call = Call::Utility(UtilityCall::force_batch {
-                        calls: calls_for_batch_clipped.clone(),
-                    });
+            // Log the batch instead of signing and submitting it
+            if config.dry_run_enabled {
+                log_dry_run_batch(&crunch, calls_for_batch_clipped).await?;
+                call_start_index = batch_range.end;
+                continue;
+            }
 
-                    debug!("call_data: 0x{}", hex::encode(batch_call.encode()));
-                }
+            // Note: Unvalidated extrinsic. If it fails a static metadata file will need to be updated!
+            let tx = batch_payload(&crunch, calls_for_batch_clipped.clone());
+
+            // Configure the transaction parameters by defining `tip` and `tx_mortal` as per user config;
+            let tx_params = if config.tx_mortal_period > 0 {
+                // Get latest block to be submitted in tx params
+                let latest_block = api.blocks().at_latest().await?;
+                TxParams::new()
+                    .tip(config.tx_tip.into())
+                    .mortal(latest_block.header(), crunch.tx_mortal_period())
+                    .build()
+            } else {
+                TxParams::new().tip(config.tx_tip.into()).build()
+            };
+
+            // Log call data in debug mode
+            if config.is_debug {
+                let batch_call = batch_call(&crunch, calls_for_batch_clipped.clone());
 
-                let mut tx_progress = api
-                    .tx()
-                    .sign_and_submit_then_watch(&tx, signer, tx_params)
-                    .await?;
-
-                while let Some(status) = tx_progress.next().await {
-                    match status? {
-                        TxStatus::InFinalizedBlock(in_block) => {
-                            // Get block number
-                            let block_number = if let Some(header) = crunch
-                                .rpc()
-                                .chain_get_header(Some(in_block.block_hash()))
-                                .await?
+                debug!("call_data: 0x{}", hex::encode(batch_call.encode()));
+            }
+
+            let mut tx_progress = signer
+                .sign_and_submit_then_watch(&api, &tx, tx_params)
+                .await?;
+            summary
+                .batch_sizes
+                .push(calls_for_batch_clipped.len() as u32);
+
+            while let Some(status) = tx_progress.next().await {
+                match status? {
+                    TxStatus::InFinalizedBlock(in_block) => {
+                        // Get block number
+                        let block_number = if let Some(header) = crunch
+                            .rpc()
+                            .chain_get_header(Some(in_block.block_hash()))
+                            .await?
+                        {
+                            header.number
+                        } else {
+                            0
+                        };
+
+                        // Fetch events from block
+                        let tx_events = in_block.fetch_events().await?;
+
+                        // Iterate over events to calculate respective reward amounts
+                        for event in tx_events.iter() {
+                            let event = event?;
+                            log_event(&event)?;
+                            if let Some(actual_fee) = get_actual_fee_paid(&event)? {
+                                // https://polkadot.js.org/docs/substrate/events#transactionfeepaidaccountid32-u128-u128
+                                // summary: A transaction fee `actual_fee`, of which `tip` was added to the minimum inclusion fee, has been paid by `who`.
+                                //
+                                summary.total_fees_paid += actual_fee;
+                            } else if let Some(amount) = get_balance_deposit(&event)? {
+                                // https://polkadot.js.org/docs/substrate/events#depositaccountid32-u128
+                                // summary: Some amount was deposited (e.g. for transaction fees).
+                                //
+                                if validator_index.is_some() {
+                                    credited_amount_value += amount;
+                                }
+                            } else if let Some(_ev) =
+                                event.as_event::<ExtrinsicFailed>()?
                             {
-                                header.number
-                            } else {
-                                0
-                            };
-
-                            // Fetch events from block
-                            let tx_events = in_block.fetch_events().await?;
-
-                            // Iterate over events to calculate respective reward amounts
-                            for event in tx_events.iter() {
-                                let event = event?;
-                                if let Some(_ev) = event.as_event::<ExtrinsicFailed>()? {
-                                    let dispatch_error = DispatchError::decode_from(
-                                        event.field_bytes(),
-                                        api.metadata(),
-                                    )?;
+                                let dispatch_error = DispatchError::decode_from(
+                                    event.field_bytes(),
+                                    api.metadata(),
+                                )?;
+                                if !config.continue_on_batch_failure_enabled {
                                     return Err(dispatch_error.into());
-                                } else if let Some(ev) =
-                                    event.as_event::<PayoutStarted>()?
-                                {
-                                    // https://polkadot.js.org/docs/substrate/events#payoutstartedu32-accountid32
-                                    // PayoutStarted(u32, AccountId32)
-                                    // summary: The stakers' rewards are getting paid. [era_index, validator_stash]
-                                    //
-                                    debug!("{:?}", ev);
-                                    let validator_index_ref = validators
-                                        .iter()
-                                        .position(|v| v.stash == ev.validator_stash);
-                                    era_index = ev.era_index;
-                                    validator_index = validator_index_ref;
-                                    validator_amount_value = 0;
-                                    nominators_amount_value = 0;
-                                    nominators_quantity = 0;
-                                } else if let Some(ev) = event.as_event::<Rewarded>()? {
-                                    // https://polkadot.js.org/docs/substrate/events#rewardedaccountid32-u128
-                                    // Rewarded(AccountId32, u128)
-                                    // summary: An account has been rewarded for their signed submission being finalized
-                                    //
-                                    debug!("{:?}", ev);
-                                    if let Some(i) = validator_index {
-                                        let validator = &mut validators[i];
-                                        if ev.stash == validator.stash {
-                                            validator_amount_value = ev.amount;
-                                        } else {
-                                            nominators_amount_value += ev.amount;
-                                            nominators_quantity += 1;
+                                }
+                                // NOTE: The failed batch is recorded and the run proceeds to the next batch
+                                warn!(
+                                    "Batch failed ({} calls): {}",
+                                    calls_for_batch_clipped.len(),
+                                    dispatch_error
+                                );
+                                summary.calls_failed +=
+                                    calls_for_batch_clipped.len() as u32;
+                                summary.batches_failed.push(FailedBatch {
+                                    block_number,
+                                    extrinsic: tx_events.extrinsic_hash(),
+                                    calls: calls_for_batch_clipped.len() as u32,
+                                    error: dispatch_error.to_string(),
+                                });
+                            } else if let Some(ev) = event.as_event::<PayoutStarted>()? {
+                                // https://polkadot.js.org/docs/substrate/events#payoutstartedu32-accountid32
+                                // PayoutStarted(u32, AccountId32)
+                                // summary: The stakers' rewards are getting paid. [era_index, validator_stash]
+                                //
+                                debug!("{:?}", ev);
+                                let validator_index_ref = validators
+                                    .iter()
+                                    .position(|v| v.stash == ev.validator_stash);
+                                era_index = ev.era_index;
+                                validator_index = validator_index_ref;
+                                validator_amount_value = 0;
+                                nominators_amount_value = 0;
+                                nominators_quantity = 0;
+                                nominators = Vec::new();
+                                credited_amount_value = 0;
+                            } else if let Some(ev) = event.as_event::<Rewarded>()? {
+                                // https://polkadot.js.org/docs/substrate/events#rewardedaccountid32-u128
+                                // Rewarded(AccountId32, u128)
+                                // summary: An account has been rewarded for their signed submission being finalized
+                                //
+                                debug!("{:?}", ev);
+                                if let Some(i) = validator_index {
+                                    let validator = &mut validators[i];
+                                    if ev.stash == validator.stash {
+                                        validator_amount_value = ev.amount;
+                                    } else {
+                                        nominators_amount_value += ev.amount;
+                                        nominators_quantity += 1;
+                                        // NOTE: Nominator detail is only kept to be exported
+                                        if config.export_enabled
+                                            && config.export_nominator_detail
+                                        {
+                                            nominators
+                                                .push((ev.stash.clone(), ev.amount));
                                         }
                                     }
-                                } else if let Some(_ev) =
-                                    event.as_event::<ItemCompleted>()?
-                                {
-                                    // https://polkadot.js.org/docs/substrate/events#itemcompleted
-                                    // summary: A single item within a Batch of dispatches has completed with no error.
-                                    //
-                                    if let Some(i) = validator_index {
-                                        let validator = &mut validators[i];
-
-                                        // NOTE: Currently we do not track which page is being payout here.
-                                        // It should be changed when payout_stakers_by_page is in place
-                                        validator.claimed.push((era_index, 0));
-                                        // Fetch stash points
-                                        let points = get_validator_points_info(
-                                            &crunch,
-                                            era_index,
-                                            &validator.stash,
-                                        )
-                                        .await?;
-
-                                        let p = Payout {
-                                            block_number,
-                                            extrinsic: tx_events.extrinsic_hash(),
-                                            era_index,
-                                            validator_amount_value,
-                                            nominators_amount_value,
-                                            nominators_quantity,
-                                            points,
-                                        };
-                                        validator.payouts.push(p);
-                                        summary.calls_succeeded += 1;
-                                    }
-                                } else if let Some(_ev) =
-                                    event.as_event::<ItemFailed>()?
-                                {
-                                    // https://polkadot.js.org/docs/substrate/events/#itemfailedspruntimedispatcherror
-                                    // summary: A single item within a Batch of dispatches has completed with error.
-                                    //
-                                    summary.calls_failed += 1;
-                                } else if let Some(_ev) =
-                                    event.as_event::<BatchCompleted>()?
-                                {
-                                    // https://polkadot.js.org/docs/substrate/events#batchcompleted
-                                    // summary: Batch of dispatches completed fully with no error.
-                                    info!(
-                                        "Batch Completed ({} calls)",
-                                        calls_for_batch_clipped.len()
-                                    );
-                                } else if let Some(_ev) =
-                                    event.as_event::<BatchCompletedWithErrors>()?
-                                {
-                                    // https://polkadot.js.org/docs/substrate/events/#batchcompletedwitherrors
-                                    // summary: Batch of dispatches completed but has errors.
-                                    info!(
-                                        "Batch Completed with errors ({} calls)",
-                                        calls_for_batch_clipped.len()
-                                    );
-                                } else if let Some(ev) =
-                                    event.as_event::<BatchInterrupted>()?
+                                }
+                            } else if let Some(_ev) = event.as_event::<ItemCompleted>()? {
+                                // https://polkadot.js.org/docs/substrate/events#itemcompleted
+                                // summary: A single item within a Batch of dispatches has completed with no error.
+                                //
+                                if let Some(i) = validator_index {
+                                    let validator = &mut validators[i];
+
+                                    // NOTE: Currently we do not track which page is being payout here.
+                                    // It should be changed when payout_stakers_by_page is in place
+                                    validator.claimed.push((era_index, 0));
+                                    // Fetch stash points
+                                    let points = get_validator_points_info(
+                                        &crunch,
+                                        era_index,
+                                        &validator.stash,
+                                    )
+                                    .await?;
+
+                                    let p = Payout {
+                                        block_number,
+                                        extrinsic: tx_events.extrinsic_hash(),
+                                        era_index,
+                                        validator_amount_value,
+                                        nominators_amount_value,
+                                        nominators_quantity,
+                                        points,
+                                        credited_amount_value: if config
+                                            .confirm_rewards_enabled
+                                        {
+                                            Some(credited_amount_value)
+                                        } else {
+                                            None
+                                        },
+                                        nominators: mem::take(&mut nominators),
+                                    };
+                                    validator.payouts.push(p);
+                                    summary.calls_succeeded += 1;
+                                }
+                            } else if let Some(_ev) = event.as_event::<ItemFailed>()? {
+                                // https://polkadot.js.org/docs/substrate/events/#itemfailedspruntimedispatcherror
+                                // summary: A single item within a Batch of dispatches has completed with error.
+                                //
+                                summary.calls_failed += 1;
+                            } else if let Some(_ev) =
+                                event.as_event::<BatchCompleted>()?
+                            {
+                                // https://polkadot.js.org/docs/substrate/events#batchcompleted
+                                // summary: Batch of dispatches completed fully with no error.
+                                info!(
+                                    "Batch Completed ({} calls)",
+                                    calls_for_batch_clipped.len()
+                                );
+                            } else if let Some(_ev) =
+                                event.as_event::<BatchCompletedWithErrors>()?
+                            {
+                                // https://polkadot.js.org/docs/substrate/events/#batchcompletedwitherrors
+                                // summary: Batch of dispatches completed but has errors.
+                                info!(
+                                    "Batch Completed with errors ({} calls)",
+                                    calls_for_batch_clipped.len()
+                                );
+                            } else if let Some(ev) =
+                                event.as_event::<BatchInterrupted>()?
+                            {
+                                // NOTE: Deprecate with force_batch
+                                //
+                                // https://polkadot.js.org/docs/substrate/events#batchinterruptedu32-spruntimedispatcherror
+                                // summary: Batch of dispatches did not complete fully. Index of first failing dispatch given, as well as the error.
+                                //
+                                // Fix: https://github.com/turboflakes/crunch/issues/4
+                                // Most likely the batch was interrupted because of an AlreadyClaimed era
+                                // BatchInterrupted { index: 0, error: Module { index: 6, error: 14 } }
+                                warn!("{:?}", ev);
+                                // NOTE: The failing call and every call after it are not dispatched
+                                if let Some(batch) = FailedBatch::interrupted(
+                                    block_number,
+                                    tx_events.extrinsic_hash(),
+                                    calls_for_batch_clipped.len(),
+                                    ev.index,
+                                    format!("{:?}", ev.error),
+                                ) {
+                                    summary.add_batch_failed(batch);
+                                }
+                                if let Some(Call::Staking(call)) =
+                                    usize::try_from(ev.index).ok().and_then(|index| {
+                                        calls_for_batch_clipped.get(index)
+                                    })
                                 {
-                                    // NOTE: Deprecate with force_batch
-                                    //
-                                    // https://polkadot.js.org/docs/substrate/events#batchinterruptedu32-spruntimedispatcherror
-                                    // summary: Batch of dispatches did not complete fully. Index of first failing dispatch given, as well as the error.
-                                    //
-                                    // Fix: https://github.com/turboflakes/crunch/issues/4
-                                    // Most likely the batch was interrupted because of an AlreadyClaimed era
-                                    // BatchInterrupted { index: 0, error: Module { index: 6, error: 14 } }
-                                    warn!("{:?}", ev);
-                                    if let Call::Staking(call) = &calls_for_batch_clipped
-                                        [usize::try_from(ev.index).unwrap()]
-                                    {
-                                        match &call {
-                                            StakingCall::payout_stakers {
-                                                validator_stash,
-                                                ..
-                                            } => {
-                                                warn!(
-                                                    "Batch interrupted at stash: {:?}",
-                                                    validator_stash
+                                    match &call {
+                                        StakingCall::payout_stakers {
+                                            validator_stash,
+                                            ..
+                                        }
+                                        | StakingCall::payout_stakers_by_page {
+                                            validator_stash,
+                                            ..
+                                        } => {
+                                            warn!(
+                                                "Batch interrupted at stash: {:?}",
+                                                validator_stash
+                                            );
+                                            let validator_index =
+                                                &mut validators.iter().position(|v| {
+                                                    v.stash == *validator_stash
+                                                });
+
+                                            if let Some(i) = *validator_index {
+                                                let validator = &mut validators[i];
+                                                // TODO: decode DispatchError to a readable format
+                                                validator.warnings.push(
+                                                    "⚡ Batch interrupted ⚡".to_string(),
                                                 );
-                                                let validator_index =
-                                                    &mut validators.iter().position(
-                                                        |v| v.stash == *validator_stash,
-                                                    );
-
-                                                if let Some(i) = *validator_index {
-                                                    let validator = &mut validators[i];
-                                                    // TODO: decode DispatchError to a readable format
-                                                    validator.warnings.push(
-                                                        "⚡ Batch interrupted ⚡"
-                                                            .to_string(),
-                                                    );
-                                                }
                                             }
-                                            _ => unreachable!(),
-                                        };
-                                    }
+                                        }
+                                        _ => unreachable!(),
+                                    };
                                 }
                             }
                         }
-                        TxStatus::Error { message } => {
-                            warn!("TxStatus: {message:?}");
-                        }
-                        TxStatus::Invalid { message } => {
-                            warn!("TxStatus: {message:?}");
-                        }
-                        TxStatus::Dropped { message } => {
-                            warn!("TxStatus: {message:?}");
-                        }
-                        _ => {}
                     }
+                    TxStatus::Error { message } => {
+                        warn!("TxStatus: {message:?}");
+                    }
+                    TxStatus::Invalid { message } => {
+                        warn!("TxStatus: {message:?}");
+                    }
+                    TxStatus::Dropped { message } => {
+                        warn!("TxStatus: {message:?}");
+                    }
+                    _ => {}
                 }
-
-                iteration = Some(x + 1);
             }
+
+            call_start_index = batch_range.end;
         }
     }
 
@@ -834,17 +1325,201 @@ pub async fn try_run_batch_payouts(
 
     // Prepare summary report
     summary.total_validators = validators.len() as u32;
+    summary.previous_era_claimed =
+        report::count_validators_claimed_in_era(validators, previous_era);
+
+    Ok(summary)
+}
+
+/// Scan unclaimed eras in chunks of `era_chunk_size` eras, from the oldest to the latest,
+/// and submit the respective payouts as soon as each chunk is scanned.
+pub async fn try_run_batch_payouts_by_era_chunks(
+    crunch: &Crunch,
+    signer: &CrunchSigner,
+    validators: &mut Validators,
+    era_index: EraIndex,
+) -> Result<PayoutSummary, CrunchError> {
+    let config = CONFIG.clone();
+
+    let start_index = get_era_index_start(&crunch, era_index).await?;
+
+    // NOTE: maximum_payouts is respected across all chunks. Unclaimed pages that go
+    // beyond it are kept aside so that they are still included in the report.
+    let maximum_payouts_overrides = get_maximum_payouts_overrides()?;
+    let mut remaining_payouts: Vec<u32> = validators
+        .iter()
+        .map(|v| {
+            match maximum_payouts_overrides
+                .get(&v.stash)
+                .copied()
+                .unwrap_or(config.maximum_payouts)
+            {
+                0 => u32::MAX,
+                maximum_payouts => maximum_payouts,
+            }
+        })
+        .collect();
+    // NOTE: max_total_calls is also respected across all chunks
+    let mut remaining_calls = if config.max_total_calls == 0 {
+        u32::MAX
+    } else {
+        config.max_total_calls
+    };
+    let mut deferred: Vec<Vec<(EraIndex, PageIndex)>> =
+        vec![Vec::new(); validators.len()];
+    let mut skipped_stashes: Vec<bool> = vec![false; validators.len()];
+    let mut summary: PayoutSummary = Default::default();
+
+    let previous_era = era_index.saturating_sub(1);
+
+    let end_index = get_era_index_end(era_index);
+    let mut chunk_start = start_index;
+    while chunk_start < end_index {
+        let chunk_end = cmp::min(chunk_start + config.era_chunk_size, end_index);
+        debug!("eras chunk [{:?} : {:?}]", chunk_start, chunk_end);
+
+        // NOTE: A stash that still fails after the storage reads are retried is skipped
+        // with a warning for the remaining chunks, so that a single stash does not fail
+        // the whole run
+        for (i, v) in validators.iter_mut().enumerate() {
+            if skipped_stashes[i] {
+                continue;
+            }
+            if let Err(e) = fetch_claimed_or_unclaimed_pages_per_era(
+                &crunch,
+                v,
+                chunk_start..chunk_end,
+                None,
+            )
+            .await
+            {
+                let warning = format!("Unclaimed eras scan failed, stash skipped: {}", e);
+                warn!("{} * {}", v.stash, warning);
+                v.claimed.clear();
+                v.unclaimed.clear();
+                v.warnings.push(warning);
+                skipped_stashes[i] = true;
+            }
+        }
+
+        // NOTE: The previous era is counted before any pages are deferred, so that
+        // pages left unclaimed because of maximum_payouts are also reported
+        if (chunk_start..chunk_end).contains(&previous_era) {
+            summary.previous_era_expected =
+                report::count_validators_with_rewards_in_era(validators, previous_era);
+        }
+
+        for (i, v) in validators.iter_mut().enumerate() {
+            // Unclaimed pages are in reverse order, keep only the oldest ones to be claimed
+            let allowed = cmp::min(remaining_payouts[i] as usize, v.unclaimed.len());
+            let n = cmp::min(allowed, remaining_calls as usize);
+            summary.calls_deferred += (allowed - n) as u32;
+            remaining_calls -= n as u32;
+            let to_claim = v.unclaimed.split_off(v.unclaimed.len() - n);
+            let mut skipped = mem::replace(&mut v.unclaimed, to_claim);
+            skipped.append(&mut deferred[i]);
+            deferred[i] = skipped;
+            remaining_payouts[i] -= allowed as u32;
+        }
+
+        let mut chunk_summary =
+            try_run_batch_payouts(&crunch, signer, validators, era_index).await?;
+        summary.calls += chunk_summary.calls;
+        summary.calls_succeeded += chunk_summary.calls_succeeded;
+        summary.calls_failed += chunk_summary.calls_failed;
+        summary.total_fees_paid += chunk_summary.total_fees_paid;
+        summary.calls_deferred += chunk_summary.calls_deferred;
+        summary
+            .batches_failed
+            .append(&mut chunk_summary.batches_failed);
+        summary.batch_sizes.append(&mut chunk_summary.batch_sizes);
+
+        // Drop the pages of the chunk once processed, only their count is kept, so that
+        // memory does not grow with the eras scanned. The last chunk is kept as is,
+        // since the previous era is still checked below
+        if chunk_end < end_index {
+            for (i, v) in validators.iter_mut().enumerate() {
+                v.unclaimed.append(&mut deferred[i]);
+                v.drop_pages();
+            }
+        }
+
+        chunk_start = chunk_end;
+    }
+
+    // Restore the pages left unclaimed
+    for (i, v) in validators.iter_mut().enumerate() {
+        v.unclaimed.append(&mut deferred[i]);
+        if v.is_active {
+            summary.next_minimum_expected += 1;
+        }
+    }
+
+    // Prepare summary report
+    summary.total_validators = validators.len() as u32;
+    summary.previous_era_claimed =
+        report::count_validators_claimed_in_era(validators, previous_era);
 
     Ok(summary)
 }
 
 async fn collect_validators_data(
     crunch: &Crunch,
+    stashes: &Vec<String>,
     era_index: EraIndex,
 ) -> Result<Validators, CrunchError> {
+    let config = CONFIG.clone();
+    let mut validators = collect_validators_info(&crunch, stashes).await?;
+
+    // Claim only the era pages defined by the user, bypassing the unclaimed eras scan
+    if !config.only_pages.is_empty() {
+        let mut era_pages = parse_era_pages(&config.only_pages).unwrap_or_default();
+        // NOTE: Unclaimed pages are kept in reverse order so that the oldest is claimed first
+        era_pages.sort();
+        era_pages.reverse();
+        for v in validators.iter_mut() {
+            v.unclaimed = era_pages.clone();
+        }
+        debug!("validators {:?}", validators);
+        return Ok(validators);
+    }
+
+    // Look for unclaimed eras, starting on current_era - maximum_eras and ending
+    // before the most recent eras skipped by the user
+    let start_index = get_era_index_start(&crunch, era_index).await?;
+    let end_index = get_era_index_end(era_index);
+
+    // NOTE: A stash that still fails after the storage reads are retried is skipped
+    // with a warning, so that a single stash does not fail the whole run
+    for v in validators.iter_mut() {
+        if let Err(e) = fetch_claimed_or_unclaimed_pages_per_era(
+            &crunch,
+            v,
+            start_index..end_index,
+            None,
+        )
+        .await
+        {
+            let warning = format!("Unclaimed eras scan failed, stash skipped: {}", e);
+            warn!("{} * {}", v.stash, warning);
+            v.claimed.clear();
+            v.unclaimed.clear();
+            v.warnings.push(warning);
+        }
+    }
+
+    debug!("validators {:?}", validators);
+    Ok(validators)
+}
+
+async fn collect_validators_info(
+    crunch: &Crunch,
+    stashes: &Vec<String>,
+) -> Result<Validators, CrunchError> {
+    let config = CONFIG.clone();
     let api = crunch.client().clone();
 
-    // Get unclaimed eras for the stash addresses
+    // Get active validators
     let active_validators_addr = node_runtime::storage().session().validators();
     let active_validators = api
         .storage()
@@ -855,8 +1530,6 @@ async fn collect_validators_data(
     debug!("active_validators {:?}", active_validators);
     let mut validators: Validators = Vec::new();
 
-    let stashes = get_stashes(&crunch).await?;
-
     for (_i, stash_str) in stashes.iter().enumerate() {
         let stash = AccountId32::from_str(stash_str).map_err(|e| {
             CrunchError::Other(format!("Invalid account: {stash_str} error: {e:?}"))
@@ -899,87 +1572,37 @@ async fn collect_validators_data(
             false
         };
 
-        // Look for unclaimed eras, starting on current_era - maximum_eras
-        let start_index = get_era_index_start(&crunch, era_index).await?;
-
-        // Get staking info from ledger
-        let ledger_addr = node_runtime::storage().staking().ledger(&controller);
-        if let Some(staking_ledger) =
-            api.storage().at_latest().await?.fetch(&ledger_addr).await?
+        // Get validator commission
+        let validator_prefs_addr = node_runtime::storage().staking().validators(&stash);
+        if let Some(prefs) = api
+            .storage()
+            .at_latest()
+            .await?
+            .fetch(&validator_prefs_addr)
+            .await?
         {
-            debug!(
-                "{} * claimed_rewards: {:?}",
-                stash, staking_ledger.legacy_claimed_rewards
-            );
-            // deconstruct claimed rewards
-            let BoundedVec(legacy_claimed_rewards) =
-                staking_ledger.legacy_claimed_rewards;
-
-            // Find unclaimed eras in previous 84 eras (reverse order)
-            for e in (start_index..era_index).rev() {
-                // TODO: legacy methods to be deprecated in the future
-                // check https://github.com/paritytech/polkadot-sdk/pull/1189
-                if legacy_claimed_rewards.contains(&e) {
-                    v.claimed.push((e, 0));
-                    continue;
-                }
-
-                // Verify if stash has claimed/unclaimed pages per era by cross checking eras_stakers_overview with claimed_rewards
-                let claimed_rewards_addr = node_runtime::storage()
-                    .staking()
-                    .claimed_rewards(&e, &stash);
-                if let Some(claimed_rewards) = api
-                    .storage()
-                    .at_latest()
-                    .await?
-                    .fetch(&claimed_rewards_addr)
-                    .await?
-                {
-                    // Verify if there are more pages to claim than the ones already claimed
-                    let eras_stakers_overview_addr = node_runtime::storage()
-                        .staking()
-                        .eras_stakers_overview(&e, &stash);
-                    if let Some(exposure) = api
-                        .storage()
-                        .at_latest()
-                        .await?
-                        .fetch(&eras_stakers_overview_addr)
-                        .await?
-                    {
-                        // Check if all pages are claimed or not
-                        for page_index in 0..exposure.page_count {
-                            if claimed_rewards.contains(&page_index) {
-                                v.claimed.push((e, page_index));
-                            } else {
-                                v.unclaimed.push((e, page_index));
-                            }
-                        }
-                    } else {
-                        // If eras_stakers_overview is not available set all pages claimed
-                        for page_index in claimed_rewards {
-                            v.claimed.push((e, page_index));
-                        }
-                    }
-                } else {
-                    // Set all pages unclaimed in case there are no claimed rewards for the era and stash specified
-                    let eras_stakers_paged_addr = node_runtime::storage()
-                        .staking()
-                        .eras_stakers_paged_iter2(&e, &stash);
-                    let mut iter = api
-                        .storage()
-                        .at_latest()
-                        .await?
-                        .iter(eras_stakers_paged_addr)
-                        .await?;
+            v.commission = Some(prefs.commission.0);
+        }
 
-                    let mut page_index = 0;
-                    while let Some(Ok(_)) = iter.next().await {
-                        v.unclaimed.push((e, page_index));
-                        page_index += 1;
-                    }
+        // Get validator reward destination
+        let payee_addr = node_runtime::storage().staking().payee(&stash);
+        if let Some(payee) = api.storage().at_latest().await?.fetch(&payee_addr).await? {
+            v.reward_destination = Some(match payee {
+                RewardDestination::Staked => report::RewardDestination::Staked,
+                RewardDestination::Stash => report::RewardDestination::Stash,
+                RewardDestination::Controller => report::RewardDestination::Controller,
+                RewardDestination::Account(account) => {
+                    report::RewardDestination::Account(account)
                 }
-            }
+                RewardDestination::None => report::RewardDestination::None,
+            });
         }
+
+        // Check if validator is at risk of being chilled or slashed
+        if config.risk_check_enabled {
+            v.risks = fetch_validator_risks(&crunch, &stash).await?;
+        }
+
         validators.push(v);
     }
 
@@ -1003,44 +1626,301 @@ async fn collect_validators_data(
         .filter(|v| v.warnings.len() == 0 && !v.has_identity)
         .collect::<Vec<Validator>>();
 
-    let mut validators = validators
-        .into_iter()
-        .filter(|v| v.warnings.len() == 0 && v.has_identity)
-        .collect::<Vec<Validator>>();
+    let mut validators = validators
+        .into_iter()
+        .filter(|v| v.warnings.len() == 0 && v.has_identity)
+        .collect::<Vec<Validator>>();
+
+    validators.sort_by(|a, b| {
+        report::replace_emoji_lowercase(&a.name)
+            .partial_cmp(&report::replace_emoji_lowercase(&b.name))
+            .unwrap()
+    });
+    validators.extend(validators_with_no_identity);
+    validators.extend(validators_with_warnings);
+
+    Ok(validators)
+}
+
+/// Look for signs that the validator has been chilled or slashed
+async fn fetch_validator_risks(
+    crunch: &Crunch,
+    stash: &AccountId32,
+) -> Result<Vec<String>, CrunchError> {
+    let api = crunch.client().clone();
+    let storage = api.storage().at_latest().await?;
+
+    let mut risks: Vec<String> = Vec::new();
+
+    // Chilled validators are no longer validator candidates
+    let validator_prefs_addr = node_runtime::storage().staking().validators(stash);
+    if storage.fetch(&validator_prefs_addr).await?.is_none() {
+        risks.push("Chilled, no longer a validator candidate".to_string());
+    }
+
+    // Slashes recorded within the slash defer duration may still be pending to be applied
+    let active_era_addr = node_runtime::storage().staking().active_era();
+    let active_era_index = match storage.fetch(&active_era_addr).await? {
+        Some(info) => info.index,
+        None => return Err(CrunchError::Other("Active era not available".into())),
+    };
+
+    let slash_defer_duration_addr =
+        node_runtime::constants().staking().slash_defer_duration();
+    let slash_defer_duration: u32 = api.constants().at(&slash_defer_duration_addr)?;
+
+    for era_index in
+        active_era_index.saturating_sub(slash_defer_duration)..=active_era_index
+    {
+        let validator_slash_addr = node_runtime::storage()
+            .staking()
+            .validator_slash_in_era(&era_index, stash);
+        if storage.fetch(&validator_slash_addr).await?.is_some() {
+            risks.push(format!("Slash recorded in era {}", era_index));
+        }
+    }
+
+    Ok(risks)
+}
+
+/// Fetch the claimed and unclaimed pages of the validator stash for the eras in range,
+/// at the block hash given or at the latest block
+async fn fetch_claimed_or_unclaimed_pages_per_era(
+    crunch: &Crunch,
+    v: &mut Validator,
+    eras: Range<EraIndex>,
+    block_hash: Option<H256>,
+) -> Result<(), CrunchError> {
+    let config = CONFIG.clone();
+
+    // Skip stashes with no controller bonded
+    let controller = match &v.controller {
+        Some(controller) => controller.clone(),
+        None => return Ok(()),
+    };
+    let stash = v.stash.clone();
+
+    // NOTE: Legacy claimed rewards are only available in the staking ledger, so the
+    // ledger lookup is skipped if the user set that legacy claimed rewards are not used
+    let legacy_claimed_rewards: Vec<EraIndex> = if config.skip_legacy_claimed_enabled {
+        Vec::new()
+    } else {
+        // Get staking info from ledger
+        let ledger_addr = node_runtime::storage().staking().ledger(&controller);
+        match crunch
+            .storage_at(block_hash)
+            .await?
+            .fetch(&ledger_addr)
+            .await?
+        {
+            Some(staking_ledger) => {
+                debug!(
+                    "{} * claimed_rewards: {:?}",
+                    stash, staking_ledger.legacy_claimed_rewards
+                );
+                // deconstruct claimed rewards
+                let BoundedVec(legacy_claimed_rewards) =
+                    staking_ledger.legacy_claimed_rewards;
+                legacy_claimed_rewards
+            }
+            None => return Ok(()),
+        }
+    };
+
+    // Find unclaimed eras in range (reverse order), scanning up to `era_scan_concurrency`
+    // eras at a time. NOTE: `buffered` yields results in the same order the eras are
+    // requested, so the resulting pages are always in the same order
+    let concurrency = cmp::max(config.era_scan_concurrency, 1) as usize;
+    // Each era is retried on transient RPC errors
+    let stash = &stash;
+    let legacy_claimed_rewards = &legacy_claimed_rewards;
+    let mut pages_per_era = stream::iter(eras.rev())
+        .map(|e| {
+            with_scan_retries(move || {
+                fetch_claimed_or_unclaimed_pages_in_era(
+                    crunch,
+                    stash,
+                    legacy_claimed_rewards,
+                    e,
+                    block_hash,
+                )
+            })
+        })
+        .buffered(concurrency);
+
+    while let Some(pages) = pages_per_era.next().await {
+        let (mut claimed, mut unclaimed) = pages?;
+        v.claimed.append(&mut claimed);
+        v.unclaimed.append(&mut unclaimed);
+    }
+    Ok(())
+}
+
+async fn fetch_claimed_or_unclaimed_pages_in_era(
+    crunch: &Crunch,
+    stash: &AccountId32,
+    legacy_claimed_rewards: &Vec<EraIndex>,
+    e: EraIndex,
+    block_hash: Option<H256>,
+) -> Result<(Vec<(EraIndex, PageIndex)>, Vec<(EraIndex, PageIndex)>), CrunchError> {
+    let config = CONFIG.clone();
+
+    let mut claimed: Vec<(EraIndex, PageIndex)> = Vec::new();
+    let mut unclaimed: Vec<(EraIndex, PageIndex)> = Vec::new();
+
+    // TODO: legacy methods to be deprecated in the future
+    // check https://github.com/paritytech/polkadot-sdk/pull/1189
+    if legacy_claimed_rewards.contains(&e) {
+        claimed.push((e, 0));
+        return Ok((claimed, unclaimed));
+    }
+
+    // Skip eras where the stash earned no reward points since there are no rewards to claim
+    if config.skip_zero_reward_eras_enabled
+        && get_validator_era_points(crunch, e, stash, block_hash).await? == 0
+    {
+        debug!("{} * era {} skipped, no reward points", stash, e);
+        return Ok((claimed, unclaimed));
+    }
+
+    let storage = crunch.storage_at(block_hash).await?;
+    let eras_stakers_overview_addr = node_runtime::storage()
+        .staking()
+        .eras_stakers_overview(&e, stash);
+
+    // Skip eras where the stash had no exposure since there is nothing to claim
+    let overview = if config.skip_inactive_eras_enabled {
+        let overview = storage.fetch(&eras_stakers_overview_addr).await?;
+        if overview.is_none() {
+            debug!("{} * era {} skipped, stash not active", stash, e);
+            return Ok((claimed, unclaimed));
+        }
+        overview
+    } else {
+        None
+    };
 
-    validators.sort_by(|a, b| {
-        report::replace_emoji_lowercase(&a.name)
-            .partial_cmp(&report::replace_emoji_lowercase(&b.name))
-            .unwrap()
-    });
-    validators.extend(validators_with_no_identity);
-    validators.extend(validators_with_warnings);
+    // Verify if stash has claimed/unclaimed pages per era by cross checking eras_stakers_overview with claimed_rewards
+    let claimed_rewards_addr =
+        node_runtime::storage().staking().claimed_rewards(&e, stash);
+    // NOTE: If the exposure overview was not fetched yet, both entries are fetched in a single request
+    let (claimed_rewards, overview) = match overview {
+        Some(overview) => (storage.fetch(&claimed_rewards_addr).await?, Some(overview)),
+        None => {
+            crunch
+                .fetch_storage_pair(
+                    &claimed_rewards_addr,
+                    &eras_stakers_overview_addr,
+                    block_hash,
+                )
+                .await?
+        }
+    };
+    if let Some(claimed_rewards) = claimed_rewards {
+        // Verify if there are more pages to claim than the ones already claimed
+        if let Some(exposure) = overview {
+            // NOTE: Eras with no pages have nothing to claim, exclude them from both lists
+            if exposure.page_count == 0 {
+                debug!("{} * era {} has no pages to claim", stash, e);
+                return Ok((claimed, unclaimed));
+            }
+            // Check if all pages are claimed or not
+            for page_index in 0..exposure.page_count {
+                if claimed_rewards.contains(&page_index) {
+                    claimed.push((e, page_index));
+                } else {
+                    unclaimed.push((e, page_index));
+                }
+            }
+        } else {
+            // If eras_stakers_overview is not available set all pages claimed
+            for page_index in claimed_rewards {
+                claimed.push((e, page_index));
+            }
+        }
+    } else {
+        // Set all pages unclaimed in case there are no claimed rewards for the era and stash specified
+        let eras_stakers_paged_addr = node_runtime::storage()
+            .staking()
+            .eras_stakers_paged_iter2(&e, stash);
+        let mut iter = storage.iter(eras_stakers_paged_addr).await?;
 
-    debug!("validators {:?}", validators);
-    Ok(validators)
+        let mut page_index = 0;
+        while let Some(Ok(_)) = iter.next().await {
+            unclaimed.push((e, page_index));
+            page_index += 1;
+        }
+    }
+    Ok((claimed, unclaimed))
+}
+
+/// Returns the staking history depth, warning if the value is implausible, which
+/// would indicate that the embedded metadata is stale or was decoded incorrectly
+fn get_history_depth(crunch: &Crunch) -> Result<u32, CrunchError> {
+    let api = crunch.client().clone();
+    let history_depth_addr = node_runtime::constants().staking().history_depth();
+    let history_depth: u32 = api.constants().at(&history_depth_addr)?;
+    debug!("history_depth {}", history_depth);
+    if !HISTORY_DEPTH_PLAUSIBLE_RANGE.contains(&history_depth) {
+        warn!(
+            "Staking history depth {} is outside the plausible range {:?}, the embedded metadata might be outdated",
+            history_depth, HISTORY_DEPTH_PLAUSIBLE_RANGE
+        );
+    }
+    Ok(history_depth)
 }
 
 async fn get_era_index_start(
     crunch: &Crunch,
     era_index: EraIndex,
 ) -> Result<EraIndex, CrunchError> {
-    let api = crunch.client().clone();
     let config = CONFIG.clone();
 
-    let history_depth_addr = node_runtime::constants().staking().history_depth();
-    let history_depth: u32 = api.constants().at(&history_depth_addr)?;
+    // Skip the history eras if only the latest completed era is to be claimed
+    if config.latest_era_only {
+        return Ok(era_index.saturating_sub(1));
+    }
+
+    let history_depth = get_history_depth(&crunch)?;
 
     if era_index < cmp::min(config.maximum_history_eras, history_depth) {
         return Ok(0);
     }
 
-    if config.is_short || config.is_medium {
+    if config.is_short || config.is_medium || config.respect_max_eras_in_verbose {
         return Ok(era_index - cmp::min(config.maximum_history_eras, history_depth));
     }
 
     // Note: If crunch is running in verbose mode, ignore MAXIMUM_ERAS
     // since we still want to show information about inclusion and eras crunched for all history_depth
-    Ok(era_index - history_depth)
+    // unless RESPECT_MAX_ERAS_IN_VERBOSE is enabled
+    Ok(era_index.saturating_sub(history_depth))
+}
+
+/// Returns the reward points earned by the validator stash in the era
+async fn get_validator_era_points(
+    crunch: &Crunch,
+    era_index: EraIndex,
+    stash: &AccountId32,
+    block_hash: Option<H256>,
+) -> Result<u32, CrunchError> {
+    let era_reward_points_addr = node_runtime::storage()
+        .staking()
+        .eras_reward_points(&era_index);
+    let points = crunch
+        .storage_at(block_hash)
+        .await?
+        .fetch(&era_reward_points_addr)
+        .await?
+        .and_then(|era_reward_points| {
+            era_reward_points
+                .individual
+                .into_iter()
+                .find(|(s, _)| *s == *stash)
+                .map(|(_, points)| points)
+        })
+        .unwrap_or_default();
+    Ok(points)
 }
 
 async fn get_validator_points_info(
@@ -1048,6 +1928,7 @@ async fn get_validator_points_info(
     era_index: EraIndex,
     stash: &AccountId32,
 ) -> Result<Points, CrunchError> {
+    let config = CONFIG.clone();
     let api = crunch.client().clone();
     // Get era reward points
     let era_reward_points_addr = node_runtime::storage()
@@ -1071,11 +1952,8 @@ async fn get_validator_points_info(
         };
 
         // Calculate average points
-        let mut points: Vec<u32> = era_reward_points
-            .individual
-            .into_iter()
-            .map(|(_, points)| points)
-            .collect();
+        let individual = era_reward_points.individual;
+        let mut points: Vec<u32> = individual.iter().map(|(_, points)| *points).collect();
 
         let points_f64: Vec<f64> = points.iter().map(|points| *points as f64).collect();
 
@@ -1084,6 +1962,11 @@ async fn get_validator_points_info(
             era_avg: stats::mean(&points_f64),
             ci99_9_interval: stats::confidence_interval_99_9(&points_f64),
             outlier_limits: stats::iqr_interval(&mut points),
+            individual: if config.export_enabled && config.export_raw_points {
+                individual
+            } else {
+                Vec::new()
+            },
         };
 
         Ok(points)
@@ -1103,15 +1986,9 @@ async fn get_display_name(
     stash: &AccountId32,
     sub_account_name: Option<String>,
 ) -> Result<(String, String, bool), CrunchError> {
-    if let Some(api) = crunch.people_client().clone() {
+    if crunch.people_client().is_some() {
         let identity_of_addr = people_runtime::storage().identity().identity_of(stash);
-        match api
-            .storage()
-            .at_latest()
-            .await?
-            .fetch(&identity_of_addr)
-            .await?
-        {
+        match crunch.fetch_people_storage(&identity_of_addr).await? {
             Some((identity, _)) => {
                 debug!("identity {:?}", identity);
                 let parent = parse_identity_data(identity.info.display);
@@ -1123,12 +2000,8 @@ async fn get_display_name(
             }
             None => {
                 let super_of_addr = people_runtime::storage().identity().super_of(stash);
-                if let Some((parent_account, data)) = api
-                    .storage()
-                    .at_latest()
-                    .await?
-                    .fetch(&super_of_addr)
-                    .await?
+                if let Some((parent_account, data)) =
+                    crunch.fetch_people_storage(&super_of_addr).await?
                 {
                     let sub_account_name = parse_identity_data(data);
                     return get_display_name(
@@ -1264,146 +2137,238 @@ fn str(bytes: Vec<u8>) -> String {
 }
 
 pub async fn inspect(crunch: &Crunch) -> Result<(), CrunchError> {
-    let api = crunch.client().clone();
+    let config = CONFIG.clone();
+
+    // Inspect storage at the block defined by the user or at the latest block
+    let block_hash = try_fetch_block_hash_from_config(&crunch).await?;
+    let (eras, summaries) = collect_stash_summaries(&crunch, block_hash).await?;
+
+    // Display a compact rollup per stash so that a backlog forming is easy to spot
+    info!("Summary in the last {} eras:", eras.len());
+    for line in report::stash_summary_table(&summaries) {
+        info!("{}", line);
+    }
+
+    if config.pool_ids.len() > 0 {
+        inspect_pools(&crunch, block_hash).await?;
+    }
+    info!("Job done!");
+
+    // Print the rollup as a single JSON line so that it can be consumed by scripts
+    if config.view_json_enabled {
+        let history_depth = get_history_depth(&crunch)?;
+        println!("{}", export::view_summary_json(history_depth, &summaries)?);
+    }
+    Ok(())
+}
 
+/// Returns the summary of claimed and unclaimed pages per stash at the latest block,
+/// without submitting any payout
+pub async fn pending(crunch: &Crunch) -> Result<Vec<StashSummary>, CrunchError> {
+    let (_, summaries) = collect_stash_summaries(&crunch, None).await?;
+    Ok(summaries)
+}
+
+/// Scan the claimed and unclaimed pages of every stash in the same eras and with the
+/// same era filters as the claim path
+async fn collect_stash_summaries(
+    crunch: &Crunch,
+    block_hash: Option<H256>,
+) -> Result<(Range<EraIndex>, Vec<StashSummary>), CrunchError> {
     let stashes = get_stashes(&crunch).await?;
     info!("Inspect {} stashes -> {}", stashes.len(), stashes.join(","));
 
-    let history_depth_addr = node_runtime::constants().staking().history_depth();
-    let history_depth: u32 = api.constants().at(&history_depth_addr)?;
+    if let Some(block_hash) = block_hash {
+        info!("Inspect at block {:?}", block_hash);
+    }
+    let storage = crunch.storage_at(block_hash).await?;
 
     let active_era_addr = node_runtime::storage().staking().active_era();
-    let active_era_index = match api
-        .storage()
-        .at_latest()
-        .await?
-        .fetch(&active_era_addr)
-        .await?
-    {
+    let active_era_index = match storage.fetch(&active_era_addr).await? {
         Some(info) => info.index,
         None => return Err(CrunchError::Other("Active era not available".into())),
     };
 
+    // Look for unclaimed eras, starting on active_era - maximum_eras and ending
+    // before the most recent eras skipped by the user
+    let start_index = get_era_index_start(&crunch, active_era_index).await?;
+    let end_index = get_era_index_end(active_era_index);
+    let eras = start_index..end_index;
+
+    let mut summaries: Vec<StashSummary> = Vec::new();
+
     for stash_str in stashes.iter() {
         let stash = AccountId32::from_str(stash_str).map_err(|e| {
             CrunchError::Other(format!("Invalid account: {stash_str} error: {e:?}"))
         })?;
         info!("{} * Stash account", stash);
 
-        let start_index = active_era_index - history_depth;
-        let mut unclaimed: Vec<(EraIndex, PageIndex)> = Vec::new();
-        let mut claimed: Vec<(EraIndex, PageIndex)> = Vec::new();
-
+        let mut v = Validator::new(stash.clone());
         let bonded_addr = node_runtime::storage().staking().bonded(&stash);
-        if let Some(controller) =
-            api.storage().at_latest().await?.fetch(&bonded_addr).await?
+        v.controller = storage.fetch(&bonded_addr).await?;
+        fetch_claimed_or_unclaimed_pages_per_era(
+            &crunch,
+            &mut v,
+            eras.clone(),
+            block_hash,
+        )
+        .await?;
+
+        info!(
+            "{} claimed pages in the last {} eras -> {:?}",
+            v.claimed.len(),
+            eras.len(),
+            v.claimed
+        );
+        info!(
+            "{} unclaimed pages in the last {} eras -> {:?}",
+            v.unclaimed.len(),
+            eras.len(),
+            v.unclaimed
+        );
+
+        let (name, _, _) = get_display_name(&crunch, &stash, None).await?;
+        summaries.push(StashSummary {
+            stash: stash.clone(),
+            name,
+            claimed: v.claimed.len(),
+            unclaimed: v.unclaimed.len(),
+            oldest_unclaimed_era: v
+                .unclaimed
+                .iter()
+                .map(|(era_index, _)| *era_index)
+                .min(),
+        });
+    }
+
+    Ok((eras, summaries))
+}
+
+/// Inspect the nomination pools defined in `pool_ids`, with amounts displayed in plancks
+async fn inspect_pools(
+    crunch: &Crunch,
+    block_hash: Option<H256>,
+) -> Result<(), CrunchError> {
+    let config = CONFIG.clone();
+    let api = crunch.client().clone();
+
+    let storage = match block_hash {
+        Some(block_hash) => api.storage().at(block_hash),
+        None => api.storage().at_latest().await?,
+    };
+
+    let ed_addr = node_runtime::constants().balances().existential_deposit();
+    let ed = api.constants().at(&ed_addr)?;
+
+    // Count members eligible for permissionless compound in each pool
+    let mut eligible_members: HashMap<u32, u32> = HashMap::new();
+    let permissions_addr = node_runtime::storage()
+        .nomination_pools()
+        .claim_permissions_iter();
+    let mut iter = storage.iter(permissions_addr).await?;
+    while let Some(Ok(kv)) = iter.next().await {
+        if [
+            ClaimPermission::PermissionlessCompound,
+            ClaimPermission::PermissionlessAll,
+        ]
+        .contains(&kv.value)
         {
-            let ledger_addr = node_runtime::storage().staking().ledger(&controller);
-            if let Some(ledger_response) =
-                api.storage().at_latest().await?.fetch(&ledger_addr).await?
-            {
-                // deconstruct claimed rewards
-                let BoundedVec(legacy_claimed_rewards) =
-                    ledger_response.legacy_claimed_rewards;
-
-                // Find unclaimed eras in previous 84 eras
-                for era_index in start_index..active_era_index {
-                    // TODO: legacy methods to be deprecated in the future
-                    // check https://github.com/paritytech/polkadot-sdk/pull/1189
-                    if legacy_claimed_rewards.contains(&era_index) {
-                        claimed.push((era_index, 0));
-                        continue;
-                    }
+            let member = get_account_id_from_storage_key(kv.key_bytes);
+            let pool_member_addr = node_runtime::storage()
+                .nomination_pools()
+                .pool_members(&member);
+            if let Some(pool_member) = storage.fetch(&pool_member_addr).await? {
+                if config.pool_ids.contains(&pool_member.pool_id) {
+                    // fetch pending rewards
+                    let call_name = format!("NominationPoolsApi_pending_rewards");
+                    let bytes = crunch
+                        .rpc()
+                        .state_call(&call_name, Some(&member.encode()), block_hash)
+                        .await?;
 
-                    // Verify if stash has claimed/unclaimed pages per era by cross checking eras_stakers_overview with claimed_rewards
-                    let claimed_rewards_addr = node_runtime::storage()
-                        .staking()
-                        .claimed_rewards(&era_index, &stash);
-                    if let Some(claimed_rewards) = api
-                        .storage()
-                        .at_latest()
-                        .await?
-                        .fetch(&claimed_rewards_addr)
-                        .await?
-                    {
-                        // Verify if there are more pages to claim than the ones already claimed
-                        let eras_stakers_overview_addr = node_runtime::storage()
-                            .staking()
-                            .eras_stakers_overview(&era_index, &stash);
-                        if let Some(exposure) = api
-                            .storage()
-                            .at_latest()
-                            .await?
-                            .fetch(&eras_stakers_overview_addr)
-                            .await?
-                        {
-                            // Check if all pages are claimed or not
-                            for page_index in 0..exposure.page_count {
-                                if claimed_rewards.contains(&page_index) {
-                                    claimed.push((era_index, page_index));
-                                } else {
-                                    unclaimed.push((era_index, page_index));
-                                }
-                            }
-                        } else {
-                            // If eras_stakers_overview is not available set all pages claimed
-                            for page_index in claimed_rewards {
-                                claimed.push((era_index, page_index));
-                            }
-                        }
-                    } else {
-                        // Set all pages unclaimed in case there are no claimed rewards for the era and stash specified
-                        let eras_stakers_paged_addr = node_runtime::storage()
-                            .staking()
-                            .eras_stakers_paged_iter2(&era_index, &stash);
-                        let mut iter = api
-                            .storage()
-                            .at_latest()
-                            .await?
-                            .iter(eras_stakers_paged_addr)
-                            .await?;
+                    let claimable: u128 = Decode::decode(&mut &*bytes)?;
 
-                        let mut page_index = 0;
-                        while let Some(Ok(_)) = iter.next().await {
-                            unclaimed.push((era_index, page_index));
-                            page_index += 1;
-                        }
+                    if claimable > config.pool_compound_threshold.into() {
+                        *eligible_members.entry(pool_member.pool_id).or_insert(0) += 1;
                     }
                 }
             }
         }
+    }
+
+    for pool_id in config.pool_ids.iter() {
+        let bonded_pool_addr = node_runtime::storage()
+            .nomination_pools()
+            .bonded_pools(pool_id);
+        let pool = match storage.fetch(&bonded_pool_addr).await? {
+            Some(pool) => pool,
+            None => {
+                warn!("Pool {} * not found", pool_id);
+                continue;
+            }
+        };
+
+        // Commission accumulated by the pool and not yet claimed
+        let reward_pool_addr = node_runtime::storage()
+            .nomination_pools()
+            .reward_pools(pool_id);
+        let commission_claimable = match storage.fetch(&reward_pool_addr).await? {
+            Some(reward_pool) => reward_pool.total_commission_pending,
+            None => 0,
+        };
+
+        // Pending rewards are kept in the pool reward account above the existential deposit
+        let reward_account = nomination_pool_account(AccountType::Reward, *pool_id);
+        let reward_account_addr =
+            node_runtime::storage().system().account(&reward_account);
+        let pending_rewards = match storage.fetch(&reward_account_addr).await? {
+            Some(info) => info.data.free.saturating_sub(ed),
+            None => 0,
+        };
+
+        info!("Pool {} * Nomination pool", pool_id);
+        info!("{} members", pool.member_counter);
+        info!("{} pending rewards", pending_rewards);
+        info!("{} commission claimable", commission_claimable);
         info!(
-            "{} claimed pages in the last {} eras -> {:?}",
-            claimed.len(),
-            history_depth,
-            claimed
-        );
-        info!(
-            "{} unclaimed pages in the last {} eras -> {:?}",
-            unclaimed.len(),
-            history_depth,
-            unclaimed
+            "{} members eligible for permissionless compound (above {} threshold)",
+            eligible_members.get(pool_id).unwrap_or(&0),
+            config.pool_compound_threshold
         );
     }
-    info!("Job done!");
+
     Ok(())
 }
 
 pub async fn get_stashes(crunch: &Crunch) -> Result<Vec<String>, CrunchError> {
     let config = CONFIG.clone();
 
-    let mut stashes: Vec<String> = config.stashes;
-    info!("{} stashes loaded from 'config.stashes'", stashes.len());
+    let mut stashes: Vec<String> = Vec::new();
 
-    if let Some(remotes) = try_fetch_stashes_from_remote_url().await? {
-        stashes.extend(remotes);
-    };
+    if config.stash_sources.contains(&StashSource::Config) {
+        info!(
+            "{} stashes loaded from 'config.stashes'",
+            config.stashes.len()
+        );
+        stashes.extend(config.stashes);
+    }
+
+    if config.stash_sources.contains(&StashSource::Remote) {
+        if let Some(remotes) = try_fetch_stashes_from_remote_url().await? {
+            stashes.extend(remotes);
+        };
+    }
 
-    if let Some(nominees) = try_fetch_stashes_from_pool_ids(&crunch).await? {
-        stashes.extend(nominees);
+    if config.stash_sources.contains(&StashSource::Pools) {
+        if let Some(nominees) = try_fetch_stashes_from_pool_ids(&crunch).await? {
+            stashes.extend(nominees);
+        }
     }
 
-    if config.unique_stashes_enabled || config.group_identity_enabled {
+    if config.unique_stashes_enabled
+        || config.group_identity_enabled
+        || config.group_identity_threshold > 0
+    {
         // sort and remove duplicates
         stashes.sort();
         stashes.dedup();
@@ -1412,6 +2377,44 @@ pub async fn get_stashes(crunch: &Crunch) -> Result<Vec<String>, CrunchError> {
     Ok(stashes)
 }
 
+pub async fn try_check_pool_ids(
+    crunch: &Crunch,
+    chain_name: String,
+) -> Result<(), CrunchError> {
+    let config = CONFIG.clone();
+    let api = crunch.client().clone();
+
+    let mut missing: Vec<String> = Vec::new();
+    for pool_id in config.pool_ids.iter() {
+        let bonded_pool_addr = node_runtime::storage()
+            .nomination_pools()
+            .bonded_pools(pool_id);
+        if api
+            .storage()
+            .at_latest()
+            .await?
+            .fetch(&bonded_pool_addr)
+            .await?
+            .is_none()
+        {
+            missing.push(pool_id.to_string());
+        }
+    }
+
+    if missing.len() > 0 {
+        if config.strict_pools {
+            return Err(CrunchError::PoolNotFound(missing.join(","), chain_name));
+        }
+        warn!(
+            "Nomination pools [{}] not found on the {} network, verify the configured pool ids",
+            missing.join(","),
+            chain_name
+        );
+    }
+
+    Ok(())
+}
+
 pub async fn try_fetch_pool_operators_for_compound(
     crunch: &Crunch,
 ) -> Result<Option<Vec<AccountId32>>, CrunchError> {
@@ -1423,6 +2426,15 @@ pub async fn try_fetch_pool_operators_for_compound(
 
     let api = crunch.client().clone();
 
+    // NOTE: Any account can compound rewards on behalf of a member with a permissionless
+    // claim permission, but only the member itself can compound without one. So if the
+    // signer is the pool depositor the permission is only checked if required by the user.
+    let signer_account_id = if config.operator_compound_requires_permission {
+        None
+    } else {
+        Some(get_signer_from_config()?.account_id())
+    };
+
     let mut members: Vec<AccountId32> = Vec::new();
 
     for pool_id in &config.pool_ids {
@@ -1440,42 +2452,84 @@ pub async fn try_fetch_pool_operators_for_compound(
                 .nomination_pools()
                 .claim_permissions(pool.roles.depositor.clone());
 
-            if let Some(permissions) = api
+            let is_permissionless = match api
                 .storage()
                 .at_latest()
                 .await?
                 .fetch(&permissions_addr)
                 .await?
             {
-                if [
+                Some(permissions) => [
                     ClaimPermission::PermissionlessCompound,
                     ClaimPermission::PermissionlessAll,
                 ]
-                .contains(&permissions)
-                {
-                    // fetch pending rewards
-                    let call_name = format!("NominationPoolsApi_pending_rewards");
-                    let bytes = crunch
-                        .rpc()
-                        .state_call(
-                            &call_name,
-                            Some(&pool.roles.depositor.clone().encode()),
-                            None,
-                        )
-                        .await?;
+                .contains(&permissions),
+                None => false,
+            };
+            let is_signer_depositor =
+                signer_account_id.as_ref() == Some(&pool.roles.depositor);
+
+            if is_permissionless || is_signer_depositor {
+                // verify that the depositor is still bonded in the pool
+                if config.pool_operator_membership_check_enabled {
+                    let pool_member_addr = node_runtime::storage()
+                        .nomination_pools()
+                        .pool_members(&pool.roles.depositor);
+                    let is_member = match api
+                        .storage()
+                        .at_latest()
+                        .await?
+                        .fetch(&pool_member_addr)
+                        .await?
+                    {
+                        Some(pool_member) => pool_member.pool_id == *pool_id,
+                        None => false,
+                    };
+                    if !is_member {
+                        info!(
+                            "Pool {} operator skipped, depositor {} is no longer a member of the pool",
+                            pool_id, pool.roles.depositor
+                        );
+                        continue;
+                    }
+                }
 
-                    let claimable: u128 = Decode::decode(&mut &*bytes)?;
+                // fetch pending rewards
+                let claimable =
+                    fetch_pool_member_pending_rewards(&crunch, &pool.roles.depositor)
+                        .await?;
 
-                    if claimable > config.pool_compound_threshold.into() {
-                        members.push(pool.roles.depositor.clone());
-                    }
+                if claimable > config.pool_compound_threshold.into() {
+                    members.push(pool.roles.depositor.clone());
                 }
+            } else {
+                debug!(
+                    "Pool {} operator skipped, no permissionless claim permission set",
+                    pool_id
+                );
             }
         }
     }
     Ok(Some(members))
 }
 
+/// Fetch the pending rewards of a pool member from the NominationPoolsApi
+async fn fetch_pool_member_pending_rewards(
+    crunch: &Crunch,
+    member: &AccountId32,
+) -> Result<u128, CrunchError> {
+    let bytes = crunch
+        .rpc()
+        .state_call(
+            "NominationPoolsApi_pending_rewards",
+            Some(&member.encode()),
+            None,
+        )
+        .await?;
+    let pending_rewards: u128 = Decode::decode(&mut &*bytes)?;
+    Ok(pending_rewards)
+}
+
 pub async fn try_fetch_pool_members_for_compound(
     crunch: &Crunch,
 ) -> Result<Option<Vec<AccountId32>>, CrunchError> {
@@ -1530,13 +2584,8 @@ pub async fn try_fetch_pool_members_for_compound(
             {
                 if config.pool_ids.contains(&pool_member.pool_id) {
                     // fetch pending rewards
-                    let call_name = format!("NominationPoolsApi_pending_rewards");
-                    let bytes = crunch
-                        .rpc()
-                        .state_call(&call_name, Some(&member.encode()), None)
-                        .await?;
-
-                    let claimable: u128 = Decode::decode(&mut &*bytes)?;
+                    let claimable =
+                        fetch_pool_member_pending_rewards(&crunch, &member).await?;
 
                     if claimable > config.pool_compound_threshold.into() {
                         members.push(member);
@@ -1597,14 +2646,15 @@ pub async fn try_fetch_stashes_from_pool_ids(
                     .collect::<Vec<String>>(),
             );
 
-            // NOTE_1: Only check active nominees from previous era
+            // NOTE_1: By default only check active nominees from previous era (CRUNCH_POOL_NOMINEE_ERA_OFFSET)
             // By the end of current era crunch will trigger any payout left from previous eras if that is the case.
             // NOTE_2: Ideally nominees shouldn't have any pending payouts, but is in the best interest of the pool members
             // that pool operators trigger payouts as a backup at least for the active nominees.
             for stash in targets {
-                let eras_stakers_addr = node_runtime::storage()
-                    .staking()
-                    .eras_stakers(era_index - 1, &stash);
+                let eras_stakers_addr = node_runtime::storage().staking().eras_stakers(
+                    era_index.saturating_sub(config.pool_nominee_era_offset),
+                    &stash,
+                );
                 if let Some(exposure) = api
                     .storage()
                     .at_latest()
@@ -42,6 +42,70 @@ impl SupportedRuntime {
         }
     }
 
+    /// Returns the default maximum number of payout calls in a single batch.
+    /// NOTE: All relay chains define the same normal class maximum extrinsic weight
+    /// (75% of a 2 seconds block) but the testnets have fewer nominators per page and
+    /// less congested blocks, so they can afford bigger batches.
+    pub fn default_maximum_calls(&self) -> u32 {
+        match &self {
+            Self::Polkadot => 4,
+            Self::Kusama => 4,
+            Self::Westend => 8,
+            Self::Paseo => 8,
+        }
+    }
+
+    /// Returns the default maximum number of pool compound calls in a single batch.
+    /// NOTE: Unlike payouts, the weight of a compound call does not depend on the number
+    /// of nominators, so a single batch holds many more calls. Polkadot and Kusama
+    /// blocks are more congested, so smaller batches are used by default.
+    pub fn default_maximum_pool_members_calls(&self) -> u32 {
+        match &self {
+            Self::Polkadot => 64,
+            Self::Kusama => 64,
+            Self::Westend => 128,
+            Self::Paseo => 128,
+        }
+    }
+
+    /// Returns the known token symbol of the network
+    pub fn token_symbol(&self) -> &str {
+        match &self {
+            Self::Polkadot => "DOT",
+            Self::Kusama => "KSM",
+            Self::Westend => "WND",
+            Self::Paseo => "PAS",
+        }
+    }
+
+    /// Returns the known token decimals of the network
+    pub fn token_decimals(&self) -> u8 {
+        match &self {
+            Self::Polkadot => 10,
+            Self::Kusama => 12,
+            Self::Westend => 12,
+            Self::Paseo => 10,
+        }
+    }
+
+    /// Returns the known genesis hash of the network
+    pub fn genesis_hash(&self) -> &str {
+        match &self {
+            Self::Polkadot => {
+                "0x91b171bb158e2d3848fa23a9f1c25182fb8e20313b2c1eb49219da7a70ce90c3"
+            }
+            Self::Kusama => {
+                "0xb0a8d493285c2df73290dfb7e61f870f17b41801197a149ca93654499ea3dafe"
+            }
+            Self::Westend => {
+                "0xe143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e"
+            }
+            Self::Paseo => {
+                "0x77afd6190f1554ad45fd0d31aee62aacc33c6db0ea801129acb813f913e0764f"
+            }
+        }
+    }
+
     pub fn chain_specs(&self) -> &str {
         match &self {
             Self::Polkadot => polkadot::POLKADOT_SPEC,
@@ -143,3 +207,30 @@ impl std::fmt::Display for SupportedParasRuntime {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_has_chain_specs_for_every_people_runtime() {
+        let runtimes = [
+            SupportedRuntime::Polkadot,
+            SupportedRuntime::Kusama,
+            SupportedRuntime::Westend,
+            SupportedRuntime::Paseo,
+        ];
+        for runtime in runtimes {
+            let relay: serde_json::Value =
+                serde_json::from_str(runtime.chain_specs()).unwrap();
+            let people_runtime = runtime.people_runtime().unwrap();
+            let people: serde_json::Value =
+                serde_json::from_str(people_runtime.chain_specs()).unwrap();
+            assert_eq!(
+                people["relay_chain"], relay["id"],
+                "{} chain specs do not belong to the {} relay chain",
+                people_runtime, runtime
+            );
+        }
+    }
+}
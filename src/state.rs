@@ -0,0 +1,237 @@
+// The MIT License (MIT)
+// Copyright © 2021 Aukbit Ltd.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::config::CONFIG;
+use crate::errors::CrunchError;
+use crate::report::{EraIndex, PayoutSummary, Validators};
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, path::Path, result::Result};
+
+/// Data persisted between runs in the file defined by `CRUNCH_STATE_PATH`
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct State {
+    /// Index of the first stash to be processed in the next run
+    #[serde(default)]
+    pub stashes_cursor: usize,
+    /// Rolling log of the latest eras processed
+    #[serde(default)]
+    pub eras: Vec<EraRecord>,
+    /// Latest run processed per identity group (empty if not grouped by identity)
+    #[serde(default)]
+    pub previous_runs: BTreeMap<String, RunRecord>,
+    /// Latest era each pool member was compounded in
+    #[serde(default)]
+    pub pool_members_compounded: BTreeMap<String, EraIndex>,
+}
+
+/// Summary of the payouts submitted for an era
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+pub struct EraRecord {
+    pub era: EraIndex,
+    pub timestamp: u64,
+    pub validators_paid: u32,
+    pub calls_succeeded: u32,
+    pub calls_failed: u32,
+}
+
+/// Outcome of a run to be compared with the next one
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+pub struct RunRecord {
+    pub calls_succeeded: u32,
+    pub calls_failed: u32,
+    pub validators_paid: u32,
+    /// Warnings raised per validator, prefixed by the validator name
+    pub warnings: Vec<String>,
+    /// Name of the validators with unclaimed pages left
+    pub behind: Vec<String>,
+}
+
+impl RunRecord {
+    pub fn from_run(validators: &Validators, summary: &PayoutSummary) -> RunRecord {
+        RunRecord {
+            calls_succeeded: summary.calls_succeeded,
+            calls_failed: summary.calls_failed,
+            validators_paid: validators.iter().filter(|v| !v.payouts.is_empty()).count()
+                as u32,
+            warnings: validators
+                .iter()
+                .flat_map(|v| v.warnings.iter().map(move |w| format!("{} {}", v.name, w)))
+                .collect(),
+            behind: validators
+                .iter()
+                .filter(|v| v.unclaimed_pages() > 0)
+                .map(|v| v.name.clone())
+                .collect(),
+        }
+    }
+}
+
+impl State {
+    /// Loads the state from file, falling back to the default state if the file
+    /// does not exist yet or can not be parsed
+    pub fn load() -> State {
+        let config = CONFIG.clone();
+        let path = Path::new(&config.state_path);
+        if !path.exists() {
+            debug!(
+                "State file {} not found, using default state",
+                config.state_path
+            );
+            return State::default();
+        }
+        match fs::read_to_string(path)
+            .map_err(CrunchError::from)
+            .and_then(|data| {
+                serde_json::from_str::<State>(&data)
+                    .map_err(|e| CrunchError::Other(e.to_string()))
+            }) {
+            Ok(state) => state,
+            Err(e) => {
+                warn!(
+                    "State file {} could not be loaded, using default state: {}",
+                    config.state_path, e
+                );
+                State::default()
+            }
+        }
+    }
+
+    /// Appends the record of an era processed to the era log, keeping at most `size`
+    /// records. Records of an era processed more than once are merged into one
+    pub fn record_era(&mut self, record: EraRecord, size: usize) {
+        match self.eras.last_mut() {
+            Some(last) if last.era == record.era => {
+                last.timestamp = record.timestamp;
+                last.validators_paid += record.validators_paid;
+                last.calls_succeeded += record.calls_succeeded;
+                last.calls_failed += record.calls_failed;
+            }
+            _ => self.eras.push(record),
+        }
+        if self.eras.len() > size {
+            self.eras.drain(..self.eras.len() - size);
+        }
+    }
+
+    /// Returns the eras after the last era recorded and before `era` that have no record
+    pub fn missing_eras(&self, era: EraIndex) -> Vec<EraIndex> {
+        match self.eras.last() {
+            Some(last) => (last.era + 1..era).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns true if the pool member was not compounded in the last `interval` eras
+    pub fn is_pool_member_due(&self, member: &str, era: EraIndex, interval: u32) -> bool {
+        match self.pool_members_compounded.get(member) {
+            Some(last) => era >= last.saturating_add(interval),
+            None => true,
+        }
+    }
+
+    /// Records the era the pool member was compounded in. Members already due again are
+    /// dropped so that the state file does not grow with members that left the pool
+    pub fn record_pool_member_compounded(
+        &mut self,
+        member: String,
+        era: EraIndex,
+        interval: u32,
+    ) {
+        self.pool_members_compounded
+            .retain(|_, last| last.saturating_add(interval) > era);
+        self.pool_members_compounded.insert(member, era);
+    }
+
+    /// Writes the state to file
+    pub fn save(&self) -> Result<(), CrunchError> {
+        let config = CONFIG.clone();
+        let data = serde_json::to_string_pretty(self)
+            .map_err(|e| CrunchError::Other(e.to_string()))?;
+        fs::write(&config.state_path, data)?;
+        debug!("State saved to {}", config.state_path);
+        Ok(())
+    }
+}
+
+/// Returns the indexes of the stashes window that starts at `cursor` and wraps
+/// around the end of the list, together with the cursor for the next run
+pub fn stashes_window(total: usize, size: usize, cursor: usize) -> (Vec<usize>, usize) {
+    if total == 0 {
+        return (Vec::new(), 0);
+    }
+    let start = cursor % total;
+    let size = std::cmp::min(size, total);
+    let indexes = (0..size).map(|i| (start + i) % total).collect();
+    (indexes, (start + size) % total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_rotates_stashes_window() {
+        assert_eq!(stashes_window(5, 2, 0), (vec![0, 1], 2));
+        assert_eq!(stashes_window(5, 2, 4), (vec![4, 0], 1));
+        // cursor out of range after the stashes list shrinks
+        assert_eq!(stashes_window(3, 2, 7), (vec![1, 2], 0));
+        assert_eq!(stashes_window(2, 4, 1), (vec![1, 0], 1));
+        assert_eq!(stashes_window(0, 4, 1), (vec![], 0));
+    }
+
+    #[test]
+    fn it_records_eras_processed() {
+        let mut state = State::default();
+        assert_eq!(state.missing_eras(10), Vec::<EraIndex>::new());
+
+        let record = |era, calls_succeeded| EraRecord {
+            era,
+            calls_succeeded,
+            ..Default::default()
+        };
+        state.record_era(record(7, 2), 2);
+        state.record_era(record(7, 3), 2);
+        assert_eq!(state.eras, vec![record(7, 5)]);
+
+        state.record_era(record(8, 1), 2);
+        state.record_era(record(9, 1), 2);
+        assert_eq!(state.eras, vec![record(8, 1), record(9, 1)]);
+        assert_eq!(state.missing_eras(10), Vec::<EraIndex>::new());
+        assert_eq!(state.missing_eras(12), vec![10, 11]);
+    }
+
+    #[test]
+    fn it_tracks_pool_members_compound_interval() {
+        let mut state = State::default();
+        assert!(state.is_pool_member_due("alice", 10, 4));
+
+        state.record_pool_member_compounded("alice".to_string(), 10, 4);
+        assert!(!state.is_pool_member_due("alice", 13, 4));
+        assert!(state.is_pool_member_due("alice", 14, 4));
+        assert!(state.is_pool_member_due("bob", 13, 4));
+
+        // members due again are dropped once another member is recorded
+        state.record_pool_member_compounded("bob".to_string(), 14, 4);
+        assert_eq!(state.pool_members_compounded.len(), 1);
+        assert!(!state.is_pool_member_due("bob", 17, 4));
+    }
+}
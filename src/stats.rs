@@ -19,6 +19,16 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
+/// Sample size from which the z-score is used instead of the critical value of the t-distribution
+const T_DISTRIBUTION_SAMPLE_THRESHOLD: usize = 30;
+
+/// Two-tailed 99.9% critical values of the t-distribution for 1 to 29 degrees of freedom
+const T_CRITICAL_VALUES_99_9: [f64; 29] = [
+    636.619, 31.599, 12.924, 8.610, 6.869, 5.959, 5.408, 5.041, 4.781, 4.587, 4.437,
+    4.318, 4.221, 4.140, 4.073, 4.015, 3.965, 3.922, 3.883, 3.850, 3.819, 3.792, 3.768,
+    3.745, 3.725, 3.707, 3.690, 3.674, 3.659,
+];
+
 pub fn mean(list: &Vec<f64>) -> f64 {
     if list.len() == 0 {
         return 0.0;
@@ -54,8 +64,16 @@ pub fn _confidence_interval_99(list: &Vec<f64>) -> (f64, f64) {
 }
 
 // Calculate 99.9% confidence interval
+// NOTE: The z-score is only valid for large samples, so for small samples the critical
+// value of the t-distribution with n - 1 degrees of freedom is used instead
 pub fn confidence_interval_99_9(list: &Vec<f64>) -> (f64, f64) {
-    confidence_interval(list, 3.291)
+    let n = list.len();
+    let critical_value = if n >= 2 && n < T_DISTRIBUTION_SAMPLE_THRESHOLD {
+        T_CRITICAL_VALUES_99_9[n - 2]
+    } else {
+        3.291
+    };
+    confidence_interval(list, critical_value)
 }
 
 // https://www.mathsisfun.com/data/confidence-interval.html
@@ -99,11 +117,17 @@ mod tests {
 
     #[test]
     fn calculate_confidence_interval_99_9() {
+        // small sample, t-distribution with 7 degrees of freedom
         let v = vec![1.0, 2.0, 3.0, 4.0, 5.0, 4.0, 2.0, 6.0];
-        assert_eq!(
-            confidence_interval_99_9(&v),
-            (1.5410332231275632, 5.208966776872437)
-        );
+        let (low, high) = confidence_interval_99_9(&v);
+        assert!((low - 0.3612982894785355).abs() < 1e-9);
+        assert!((high - 6.388701710521465).abs() < 1e-9);
+
+        // large sample, z-score
+        let v: Vec<f64> = (0..35).map(|i| (i % 7) as f64).collect();
+        let (low, high) = confidence_interval_99_9(&v);
+        assert!((low - 1.8874389390753807).abs() < 1e-9);
+        assert!((high - 4.1125610609246195).abs() < 1e-9);
     }
 
     #[test]